@@ -1,27 +1,43 @@
 // checklist before publishing
 // https://rust-lang.github.io/api-guidelines
 
-// pub mod pdf_attach;
-// pub mod pdf_detach;
-// pub mod pdf_fonts;
-// pub mod pdf_images;
+pub mod pdf_attach;
+pub mod pdf_detach;
+pub mod pdf_fonts;
+pub mod pdf_images;
 pub mod pdf_info;
-// pub mod pdf_separate;
-// pub mod pdf_sig;
-// pub mod pdf_to_cairo;
-// pub mod pdf_to_ppm;
-// pub mod pdf_to_ps;
+pub mod pdf_separate;
+pub mod pdf_sig;
+pub mod pdf_to_cairo;
 pub mod pdf_to_html;
+pub mod pdf_to_ppm;
+pub mod pdf_to_ps;
 pub mod pdf_to_text;
-// pub mod pdf_unite;
+pub mod pdf_unite;
 pub mod utils;
 
 #[cfg(test)]
 mod tests {
-    use crate::pdf_info::{pdf_info, PdfInfoConfig};
+    use crate::pdf_attach::pdf_attach;
+    use crate::pdf_detach::{pdf_detach_extract, DetachTarget, PdfDetachConfig};
+    use crate::pdf_fonts::{pdf_fonts, FontType, ObjectId, PdfFontsConfig};
+    use crate::pdf_images::{pdf_images, ImageEncoding, ImageType, PdfImagesConfig};
+    use crate::pdf_info::{
+        has_mixed_orientations, has_mixed_page_sizes, pdf_info, EncryptionAlgorithm, FormType,
+        Orientation, PdfDate, PdfInfoConfig, Rect,
+    };
+    use crate::pdf_separate::{pdf_separate, PdfSeparateConfig};
+    use crate::pdf_sig::{pdf_sig, CertificateStatus, PdfSigConfig, SignatureStatus};
+    use crate::pdf_to_cairo::{pdf_to_cairo, CairoFormat, PdfToCairoConfig};
     use crate::pdf_to_html::{pdf_to_html, PdfToHtmlConfig};
-    use crate::pdf_to_text::{pdf_to_text, PdfToTextConfig};
-    use crate::utils::AsPopplerPath;
+    use crate::pdf_to_ppm::{pdf_to_ppm, ImageFormat, PdfToPpmConfig};
+    use crate::pdf_to_ps::{pdf_to_ps, PdfToPsConfig, PsLevel, RasterizeMode};
+    use crate::pdf_to_text::{
+        extract_region, extract_regions, group_tsv_rows, pdf_to_layout, pdf_to_text,
+        pdf_to_text_pages, pdf_to_tsv, pdf_to_words, PdfToTextConfig, TsvLevel, Units,
+    };
+    use crate::pdf_unite::{pdf_unite, PdfUniteConfig};
+    use crate::utils::{AsPopplerBuffer, AsPopplerPath};
     use tokio::runtime::Builder;
 
     // #[test]
@@ -50,6 +66,512 @@ mod tests {
         })
     }
 
+    #[test]
+    fn parse_pdf_info_works() {
+        let stdout = "Title:           Quarterly report\n\
+                      second line\n\
+                      Producer:        LibreOffice 7.5\n\
+                      CreationDate:    2023-04-01T10:20:30+02\n\
+                      Custom Metadata: no\n\
+                      Metadata Stream: yes\n\
+                      Tagged:          yes\n\
+                      UserProperties:  no\n\
+                      Suspects:        no\n\
+                      Form:            AcroForm\n\
+                      JavaScript:      no\n\
+                      Pages:           4\n\
+                      Encrypted:       yes (print:yes copy:no change:no addNotes:no algorithm:AES)\n\
+                      Page size:       595.276 x 841.89 pts (A4)\n\
+                      Page rot:        90\n\
+                      File size:       31337 bytes\n\
+                      Optimized:       no\n\
+                      PDF version:     1.7\n\
+                      PDF subtype:     PDF/A-1b\n    \
+                      Title:         ISO 19005 - Electronic document file format\n    \
+                      Conformance:   B";
+
+        let info = crate::pdf_info::parse_pdf_info(stdout).unwrap();
+        assert_eq!(info.title.as_deref(), Some("Quarterly report\nsecond line"));
+        assert_eq!(info.author, None);
+        assert_eq!(info.producer.as_deref(), Some("LibreOffice 7.5"));
+        assert_eq!(
+            info.creation_date.map(PdfDate::to_iso_string).as_deref(),
+            Some("2023-04-01T10:20:30+02:00")
+        );
+        assert!(info.metadata_stream && info.tagged && !info.custom_metadata);
+        assert_eq!(info.form, FormType::AcroForm);
+        assert_eq!(info.pages, 4);
+        let encryption = info.encryption.unwrap();
+        assert_eq!(encryption.algorithm, EncryptionAlgorithm::Aes);
+        assert_eq!(encryption.key_length, Some(128));
+        assert_eq!(encryption.permissions.assemble, None);
+        assert!(encryption.permissions.print && !encryption.permissions.add_notes);
+        assert!(!info.can_extract_text());
+        let page_size = info.page_size.unwrap();
+        assert_eq!((page_size.width, page_size.height), (595.276, 841.89));
+        assert_eq!(page_size.paper.as_deref(), Some("A4"));
+        assert_eq!(info.page_rotation, 90);
+        assert_eq!(info.file_size, 31337);
+        assert_eq!(info.pdf_version, "1.7");
+
+        assert!(crate::pdf_info::parse_pdf_info("Syntax Error: broken").is_err());
+
+        let plain =
+            crate::pdf_info::parse_pdf_info("Pages:           1\nEncrypted:       no").unwrap();
+        assert!(plain.encryption.is_none() && plain.can_extract_text());
+        let rc4 = crate::pdf_info::parse_pdf_info(
+            "Pages:           1\n\
+             Encrypted:       yes (print:no copy:yes change:no addNotes:no algorithm:RC4)",
+        )
+        .unwrap();
+        let encryption = rc4.encryption.unwrap();
+        assert_eq!(encryption.algorithm, EncryptionAlgorithm::Rc4);
+        assert_eq!(encryption.key_length, None);
+        assert!(rc4.can_extract_text());
+        assert!(crate::pdf_info::parse_pdf_info(
+            "Pages:           1\nEncrypted:       yes (print:yes algorithm:AES)"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn pdf_info_parsed_keeps_page_size_of_later_first_page() {
+        let options = crate::pdf_info::parsed_info_options(PdfInfoConfig {
+            first_page: 3,
+            ..PdfInfoConfig::default()
+        });
+        assert_eq!(options, vec!["-f", "3", "-l", "3", "-rawdates"]);
+
+        let info = crate::pdf_info::parse_pdf_info(
+            "Pages:           4\n\
+             Encrypted:       no\n\
+             Page    3 size:  612 x 792 pts (letter)\n\
+             Page    3 rot:   180",
+        )
+        .unwrap();
+        assert_eq!(info.page_size.unwrap().paper.as_deref(), Some("letter"));
+        assert_eq!(info.page_rotation, 180);
+    }
+
+    #[test]
+    fn parse_xmp_metadata_works() {
+        let xml = r#"<?xpacket begin="" id="W5M0MpCehiHzreSzNTczkc9d"?>
+<x:xmpmeta xmlns:x="adobe:ns:meta/">
+  <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+    <rdf:Description rdf:about=""
+        xmlns:pdfaid="http://www.aiim.org/pdfa/ns/id/"
+        pdfaid:part="2" pdfaid:conformance="B"
+        xmlns:xmp="http://ns.adobe.com/xap/1.0/"
+        xmp:CreateDate="2023-04-01T10:20:30+02:00">
+      <xmp:CreatorTool>Writer</xmp:CreatorTool>
+    </rdf:Description>
+    <rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/">
+      <dc:title>
+        <rdf:Alt>
+          <rdf:li xml:lang="de">Bericht</rdf:li>
+          <rdf:li xml:lang="x-default">Report &amp; Summary</rdf:li>
+        </rdf:Alt>
+      </dc:title>
+      <dc:creator><rdf:Seq><rdf:li>Ada</rdf:li><rdf:li>Grace</rdf:li></rdf:Seq></dc:creator>
+      <dc:subject><rdf:Bag><rdf:li>finance</rdf:li></rdf:Bag></dc:subject>
+    </rdf:Description>
+    <rdf:Description rdf:about="" xmlns:p="http://ns.adobe.com/pdf/1.3/">
+      <p:Producer>LibreOffice 7.5</p:Producer>
+      <p:Keywords/>
+    </rdf:Description>
+  </rdf:RDF>
+</x:xmpmeta>
+<?xpacket end="w"?>"#;
+
+        let metadata = crate::pdf_info::parse_xmp_metadata(xml).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Report & Summary"));
+        assert_eq!(metadata.creators, vec!["Ada", "Grace"]);
+        assert_eq!(metadata.subject, vec!["finance"]);
+        assert_eq!(metadata.description, None);
+        assert_eq!(
+            metadata.create_date,
+            PdfDate::parse("D:20230401102030+02'00'")
+        );
+        assert_eq!(metadata.creator_tool.as_deref(), Some("Writer"));
+        assert_eq!(metadata.producer.as_deref(), Some("LibreOffice 7.5"));
+        assert_eq!(metadata.keywords, None);
+        let pdfa = metadata.pdfa.unwrap();
+        assert_eq!(
+            (pdfa.part, pdfa.conformance.as_deref()),
+            (Some(2), Some("B"))
+        );
+        assert_eq!(metadata.raw, xml);
+
+        let odd_part = xml.replace(r#"pdfaid:part="2""#, r#"pdfaid:part="2b""#);
+        let metadata = crate::pdf_info::parse_xmp_metadata(&odd_part).unwrap();
+        assert_eq!(metadata.title.as_deref(), Some("Report & Summary"));
+        let pdfa = metadata.pdfa.unwrap();
+        assert_eq!((pdfa.part, pdfa.conformance.as_deref()), (None, Some("B")));
+    }
+
+    #[test]
+    fn pdf_date_works() {
+        let date = PdfDate::parse("D:20230401102030+02'00'").unwrap();
+        assert_eq!(date.timestamp, 1680337230);
+        assert_eq!(date.offset_minutes, Some(120));
+        assert_eq!(date.to_iso_string(), "2023-04-01T10:20:30+02:00");
+        assert_eq!(date.to_raw_string(), "D:20230401102030+02'00'");
+        assert_eq!(date.to_locale_string(), "Sat Apr  1 08:20:30 2023 UTC");
+
+        // the same time in every form pdfinfo and XMP write
+        for value in [
+            "2023-04-01T10:20:30+02",
+            "2023-04-01T10:20:30+02:00",
+            "2023-04-01T08:20:30Z",
+            "20230401102030+0200",
+            "Sat Apr  1 08:20:30 2023 UTC",
+            "Sat 01 Apr 2023 08:20:30 AM UTC",
+        ] {
+            let parsed = PdfDate::parse(value).unwrap();
+            assert_eq!(parsed.timestamp, date.timestamp, "{}", value);
+        }
+
+        let partial = PdfDate::parse("D:2023").unwrap();
+        assert_eq!(partial.to_raw_string(), "D:20230101000000");
+        assert_eq!(partial.offset_minutes, None);
+        assert_eq!(
+            PdfDate::parse("2023-04").unwrap().to_iso_string(),
+            "2023-04-01T00:00:00Z"
+        );
+        let west = PdfDate::parse("D:20230401030030-05'30'").unwrap();
+        assert_eq!(west.to_iso_string(), "2023-04-01T03:00:30-05:30");
+
+        // later in UTC even though earlier on the clock
+        assert!(west > date);
+        assert!(partial < date);
+
+        assert_eq!(PdfDate::parse("yesterday"), None);
+        assert_eq!(PdfDate::parse("D:20x3"), None);
+        assert_eq!(PdfDate::parse("2023-13-01"), None);
+        assert_eq!(PdfDate::parse("D:20230231"), None);
+        assert_eq!(PdfDate::parse("2023-04-31"), None);
+        assert!(PdfDate::parse("D:20240229").is_some());
+        assert_eq!(PdfDate::parse("D:21000229"), None);
+    }
+
+    #[test]
+    fn parse_custom_info_works() {
+        use crate::pdf_info::{parse_custom_info, InfoValue};
+
+        let stdout = "Author:          Ada\n\
+                      CreationDate:    Sat Apr  1 08:20:30 2023 UTC\n\
+                      ExportedAt:      D:20230401102030+02'00'\n\
+                      Notes:           first line\n\
+                      second line\n\
+                      ReviewedAt:      D:20230231\n\
+                      TrackingIdentifier:AB-1234";
+
+        let custom = parse_custom_info(stdout);
+        assert_eq!(
+            custom.keys().collect::<Vec<_>>(),
+            vec!["ExportedAt", "Notes", "ReviewedAt", "TrackingIdentifier"]
+        );
+        assert_eq!(
+            custom["ExportedAt"],
+            InfoValue::Date(PdfDate::parse("2023-04-01T10:20:30+02:00").unwrap())
+        );
+        assert_eq!(
+            custom["Notes"],
+            InfoValue::Text("first line\nsecond line".to_string())
+        );
+        // there is no February 31st
+        assert_eq!(
+            custom["ReviewedAt"],
+            InfoValue::Text("D:20230231".to_string())
+        );
+        assert_eq!(
+            custom["TrackingIdentifier"],
+            InfoValue::Text("AB-1234".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_javascript_works() {
+        use crate::pdf_info::{parse_javascript, ScriptLocation};
+
+        let stdout = "Name Dictionary \"init\":\n\
+                      app.alert('hi');\n\
+                      \n\
+                      label:\n\
+                      \n\
+                      Page Open:\n\
+                      this.print();\n\
+                      \n\
+                      Widget Annotation Cursor Enter (Rendition):\n\
+                      play();";
+
+        let scripts = parse_javascript(stdout).unwrap();
+        assert_eq!(scripts.len(), 3);
+        assert_eq!(
+            scripts[0].location,
+            ScriptLocation::NameTree("init".to_string())
+        );
+        assert_eq!(scripts[0].trigger, None);
+        assert_eq!(scripts[0].source, "app.alert('hi');\n\nlabel:");
+        assert_eq!(scripts[1].location, ScriptLocation::Page);
+        assert_eq!(scripts[1].trigger.as_deref(), Some("Page Open"));
+        assert_eq!(scripts[1].source, "this.print();");
+        assert_eq!(scripts[2].location, ScriptLocation::Annotation);
+        assert!(scripts[2].rendition);
+        assert_eq!(scripts[2].source, "play();");
+
+        assert!(parse_javascript("").unwrap().is_empty());
+
+        // label lines inside a source do not start scripts of their own
+        let stdout = "Field Activated:\n\
+                      var a = 1;\n\
+                      Page Open:\n\
+                      \n\
+                      Before Close Document:\n\
+                      \n\
+                      Link Annotation Activated:\n\
+                      run();\n\
+                      \n\
+                      Name Dictionary \"init\":\n\
+                      \n";
+        let scripts = parse_javascript(stdout).unwrap();
+        assert_eq!(scripts.len(), 2);
+        assert_eq!(scripts[0].location, ScriptLocation::Field);
+        assert_eq!(
+            scripts[0].source,
+            "var a = 1;\nPage Open:\n\nBefore Close Document:"
+        );
+        assert_eq!(scripts[1].location, ScriptLocation::Annotation);
+        assert_eq!(scripts[1].source, "run();\n\nName Dictionary \"init\":");
+        assert_eq!(scripts[1].page, None);
+    }
+
+    #[test]
+    fn parse_struct_tree_works() {
+        use crate::pdf_info::{parse_struct_tree, StructDisplay};
+
+        let stdout = "Document\n  \
+                      H1 <h-1> \"Intro\" r2 (block):\n     \
+                      /Placement /Block\n     \
+                      /UserProperty (Owner) (Ada) \"Ada\" [hidden]\n    \
+                      \"Getting \n\
+                      started\"\n  \
+                      Figure (block)\n    \
+                      Object 12 0\n  \
+                      P\n    \
+                      (No content?)\n\
+                      Art";
+
+        let tree = parse_struct_tree(stdout).unwrap();
+        assert_eq!(tree.roots.len(), 2);
+        let roles = tree
+            .iter()
+            .map(|node| node.role.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(roles, vec!["Document", "H1", "Figure", "P", "Art"]);
+
+        let heading = &tree.roots[0].children[0];
+        assert_eq!(heading.id.as_deref(), Some("h-1"));
+        assert_eq!(heading.title.as_deref(), Some("Intro"));
+        assert_eq!(heading.revision, 2);
+        assert_eq!(heading.display, Some(StructDisplay::Block));
+        assert_eq!(heading.text.as_deref(), Some("Getting \nstarted"));
+        assert_eq!(heading.attributes.len(), 2);
+        assert_eq!(heading.attributes[0].name, "Placement");
+        assert_eq!(heading.attributes[0].value, "/Block");
+        let property = &heading.attributes[1];
+        assert_eq!(property.user_property.as_deref(), Some("Owner"));
+        assert_eq!(property.value, "(Ada)");
+        assert_eq!(property.formatted_value.as_deref(), Some("Ada"));
+        assert!(property.hidden);
+
+        let figure = &tree.roots[0].children[1];
+        assert_eq!(figure.objects, vec![ObjectId { num: 12, gen: 0 }]);
+        assert_eq!(tree.roots[0].children[2].text, None);
+        assert_eq!(tree.roots[0].iter().count(), 4);
+
+        assert!(parse_struct_tree("    P").is_err());
+    }
+
+    #[test]
+    fn parse_destinations_works() {
+        use crate::pdf_info::{missing_destinations, parse_destinations, DestinationKind};
+
+        let stdout = "Page  Destination                 Name\n   \
+                      1 [ XYZ    0  792 null      ] \"chapter-1\"\n   \
+                      1 [ Fit                     ] \"cover page\"\n  \
+                      12 [ FitR   10   20  300  400] \"figure \"2\"\"\n  \
+                      12 [ FitBH null             ] \"end\"\n  \
+                      12 [ FitR 10000 2000 3000 400] \"poster\"\n  \
+                      12 [ FitR  -1000 -1000 -1000 -] \"below\"";
+
+        let destinations = parse_destinations(stdout).unwrap();
+        assert_eq!(destinations.len(), 6);
+        assert_eq!(destinations[0].name, "chapter-1");
+        assert_eq!(destinations[0].kind, DestinationKind::Xyz);
+        assert_eq!(
+            (
+                destinations[0].left,
+                destinations[0].top,
+                destinations[0].zoom
+            ),
+            (Some(0.0), Some(792.0), None)
+        );
+        assert_eq!(destinations[1].name, "cover page");
+        assert_eq!(destinations[1].kind, DestinationKind::Fit);
+        assert_eq!(destinations[2].page, 12);
+        assert_eq!(destinations[2].name, "figure \"2\"");
+        assert_eq!(
+            (destinations[2].left, destinations[2].bottom),
+            (Some(10.0), Some(20.0))
+        );
+        // the top runs up to the ']' and may be cut
+        assert_eq!(
+            (destinations[2].right, destinations[2].top),
+            (Some(300.0), None)
+        );
+        assert_eq!(destinations[3].kind, DestinationKind::FitBH);
+        assert_eq!(destinations[3].top, None);
+        assert_eq!(
+            (destinations[4].left, destinations[4].right),
+            (Some(10000.0), Some(3000.0))
+        );
+        assert_eq!(destinations[4].top, None);
+        // cut down to the sign
+        assert_eq!(
+            (destinations[5].left, destinations[5].right),
+            (Some(-1000.0), Some(-1000.0))
+        );
+        assert_eq!(destinations[5].top, None);
+
+        assert_eq!(
+            missing_destinations(&destinations, &["chapter-1", "chapter-2", "end"]),
+            vec!["chapter-2"]
+        );
+
+        assert!(parse_destinations("Page  Destination                 Name")
+            .unwrap()
+            .is_empty());
+        assert!(parse_destinations("Page  Destination\n   1 [ Zoom ] \"x\"").is_err());
+    }
+
+    #[test]
+    fn parse_urls_works() {
+        use crate::pdf_info::{parse_urls, unique_urls, UrlKind};
+
+        let stdout = "Page  Type          URL\n   \
+                      1  Annotation    https://User@Example.COM:8443/a b?q=1#top\n   \
+                      1  Annotation    https://User@Example.COM:8443/a b?q=1#top\n   \
+                      2  Annotation    https://User@Example.COM:8443/a b?q=1#top\n   \
+                      2  Annotation    mailto:help@Support.org?subject=hi\n   \
+                      2  Annotation    file:///etc/passwd\n   \
+                      3  Annotation     JavaScript:app.alert(1)\n   \
+                      3  Annotation    C:\\Windows\\calc.exe\n   \
+                      3  Annotation    chapter2.html";
+
+        let links = parse_urls(stdout).unwrap();
+        assert_eq!(links.len(), 7);
+        let url = &links[0].url;
+        assert_eq!(links[0].kind, UrlKind::Http);
+        assert_eq!(url.scheme.as_deref(), Some("https"));
+        assert_eq!(url.user_info.as_deref(), Some("User"));
+        assert_eq!(url.host.as_deref(), Some("example.com"));
+        assert_eq!(url.port, Some(8443));
+        assert_eq!(url.path, "/a b");
+        assert_eq!(url.query.as_deref(), Some("q=1"));
+        assert_eq!(url.fragment.as_deref(), Some("top"));
+        assert_eq!(links[1].page, 2);
+
+        assert_eq!(links[2].kind, UrlKind::Mailto);
+        assert_eq!(links[2].url.domain().as_deref(), Some("support.org"));
+        assert_eq!(links[3].kind, UrlKind::File);
+        assert_eq!(links[3].url.path, "/etc/passwd");
+        assert_eq!(links[4].kind, UrlKind::JavaScript);
+        assert_eq!(links[5].kind, UrlKind::File);
+        assert_eq!(links[6].kind, UrlKind::Other);
+        assert_eq!(links[6].url.scheme, None);
+
+        assert_eq!(unique_urls(&links).len(), 6);
+    }
+
+    #[test]
+    fn link_url_reads_like_browsers() {
+        use crate::pdf_info::{LinkUrl, UrlKind};
+
+        let url = LinkUrl::parse("https://evil.example\\@trusted.example/");
+        assert_eq!(url.host.as_deref(), Some("evil.example"));
+        assert_eq!(url.user_info, None);
+        assert_eq!(url.path, "/@trusted.example/");
+
+        let url = LinkUrl::parse("java\tscript:alert(1)");
+        assert_eq!(url.kind(), UrlKind::JavaScript);
+        assert_eq!(url.raw, "java\tscript:alert(1)");
+        assert_eq!(
+            LinkUrl::parse("jav\r\nascript:x").kind(),
+            UrlKind::JavaScript
+        );
+
+        assert_eq!(
+            LinkUrl::parse("https:\\\\Evil.example/a").host.as_deref(),
+            Some("evil.example")
+        );
+        assert_eq!(
+            LinkUrl::parse("http:evil.example").host.as_deref(),
+            Some("evil.example")
+        );
+        // '\' only separates in the schemes browsers treat specially
+        assert_eq!(
+            LinkUrl::parse("foo://a.example\\@b.example/")
+                .host
+                .as_deref(),
+            Some("b.example")
+        );
+    }
+
+    #[test]
+    fn parse_page_geometry_works() {
+        let stdout = "Pages:           2\n\
+                      Page    1 size:  612 x 792 pts (letter)\n\
+                      Page    1 rot:   0\n\
+                      Page    2 size:  612 x 792 pts (letter)\n\
+                      Page    2 rot:   90\n\
+                      Page    1 MediaBox:      0.00     0.00   612.00   792.00\n\
+                      Page    1 CropBox:       0.00     0.00   612.00   792.00\n\
+                      Page    1 BleedBox:      0.00     0.00   612.00   792.00\n\
+                      Page    1 TrimBox:      18.00    18.00   594.00   774.00\n\
+                      Page    1 ArtBox:        0.00     0.00   612.00   792.00\n\
+                      Page    2 MediaBox:      0.00     0.00   612.00   792.00\n\
+                      Page    2 CropBox:       0.00     0.00   612.00   792.00\n\
+                      Page    2 BleedBox:      0.00     0.00   612.00   792.00\n\
+                      Page    2 TrimBox:       0.00     0.00   612.00   792.00\n\
+                      Page    2 ArtBox:        0.00     0.00   612.00   792.00\n\
+                      File size:       1024 bytes";
+
+        let pages = crate::pdf_info::parse_page_geometry(stdout).unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].number, 1);
+        assert_eq!(pages[0].size.paper.as_deref(), Some("letter"));
+        assert_eq!(
+            pages[0].trim_box,
+            Rect {
+                x_min: 18.0,
+                y_min: 18.0,
+                x_max: 594.0,
+                y_max: 774.0
+            }
+        );
+        assert_eq!(pages[0].trim_box.width(), 576.0);
+        assert_eq!(pages[0].orientation(), Orientation::Portrait);
+        assert_eq!(pages[1].rotation, 90);
+        assert_eq!(pages[1].orientation(), Orientation::Landscape);
+        assert!(!has_mixed_page_sizes(&pages));
+        assert!(has_mixed_orientations(&pages));
+
+        // a page without its boxes
+        let stdout = "Page    1 size:  612 x 792 pts (letter)\nPage    1 rot:   0";
+        assert!(crate::pdf_info::parse_page_geometry(stdout).is_err());
+    }
+
     #[test]
     fn pdf_to_html_works() {
         run_test(async {
@@ -67,6 +589,52 @@ mod tests {
         })
     }
 
+    #[test]
+    fn pdf_to_ps_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfToPsConfig {
+                level: PsLevel::Level3,
+                rasterize: RasterizeMode::Never,
+                ..PdfToPsConfig::default()
+            };
+
+            let _result = pdf_to_ps(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_to_ps_parses_options() {
+        use crate::pdf_to_ps::parse_options;
+
+        // level 2 and rasterizing when needed are pdftops' defaults
+        assert!(parse_options(&PdfToPsConfig::default()).is_empty());
+
+        let config = PdfToPsConfig {
+            eps_file: true,
+            level: PsLevel::Level3,
+            rasterize: RasterizeMode::Never,
+            ..PdfToPsConfig::default()
+        };
+        assert_eq!(
+            parse_options(&config),
+            vec!["-eps", "-level3", "-rasterize", "never"]
+        );
+
+        let config = PdfToPsConfig {
+            level: PsLevel::Level1Sep,
+            rasterize: RasterizeMode::Always,
+            ..PdfToPsConfig::default()
+        };
+        assert_eq!(
+            parse_options(&config),
+            vec!["-level1sep", "-rasterize", "always"]
+        );
+    }
+
     #[test]
     fn pdf_to_text_works() {
         run_test(async {
@@ -84,9 +652,780 @@ mod tests {
         })
     }
 
+    #[test]
+    fn pdf_to_text_pages_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfToTextConfig::default();
+
+            let _result = pdf_to_text_pages(file, config).await;
+        })
+    }
+
+    #[test]
+    fn parse_text_pages_works() {
+        let stdout = "  Title\n\nFirst page\n\x0c\x0cThird page\n\x0c";
+        let pages = crate::pdf_to_text::parse_text_pages(stdout, 5);
+        let pages: Vec<(u32, &str)> = pages
+            .iter()
+            .map(|page| (page.number, page.text.as_str()))
+            .collect();
+        assert_eq!(
+            pages,
+            vec![(5, "  Title\n\nFirst page\n"), (6, ""), (7, "Third page\n")]
+        );
+
+        assert!(crate::pdf_to_text::parse_text_pages("", 1).is_empty());
+        assert_eq!(crate::pdf_to_text::parse_text_pages("\x0c", 1).len(), 1);
+    }
+
+    #[test]
+    fn extract_region_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let total = Rect {
+                x_min: 120.0,
+                y_min: 250.0,
+                x_max: 190.0,
+                y_max: 262.0,
+            };
+            let file = file_path.as_poppler_path();
+            let result = extract_region(
+                file,
+                0,
+                total,
+                Units::Millimetres,
+                PdfToTextConfig::default(),
+            )
+            .await;
+            // pages count from 1, page 0 is refused before pdftotext runs
+            assert!(result.is_err());
+        })
+    }
+
+    #[test]
+    fn extract_regions_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let total = Rect {
+                x_min: 120.0,
+                y_min: 250.0,
+                x_max: 190.0,
+                y_max: 262.0,
+            };
+            let file = file_path.as_poppler_path();
+            let regions = [("total", total), ("date", Rect::default())];
+            let refused = extract_regions(
+                file.clone(),
+                1,
+                &regions,
+                Units::Millimetres,
+                PdfToTextConfig::default(),
+            )
+            .await
+            .is_err();
+            // the empty date region is refused before pdftotext runs
+            assert!(refused);
+
+            // and so is a name given twice
+            let regions = [("total", total), ("total", total)];
+            let error_kind = extract_regions(
+                file,
+                1,
+                &regions,
+                Units::Millimetres,
+                PdfToTextConfig::default(),
+            )
+            .await
+            .map_err(|e| e.kind())
+            .err();
+            assert_eq!(error_kind, Some(std::io::ErrorKind::InvalidInput));
+        })
+    }
+
+    #[test]
+    fn crop_args_works() {
+        let region = Rect {
+            x_min: 10.2,
+            y_min: 20.0,
+            x_max: 110.5,
+            y_max: 60.0,
+        };
+        let args = crate::pdf_to_text::crop_args(1, &region, Units::Points).unwrap();
+        assert_eq!(args, vec!["-x", "10", "-y", "20", "-W", "101", "-H", "40"]);
+
+        let inch = Rect {
+            x_min: 25.4,
+            y_min: 0.0,
+            x_max: 50.8,
+            y_max: 25.4,
+        };
+        let args = crate::pdf_to_text::crop_args(1, &inch, Units::Millimetres).unwrap();
+        assert_eq!(args, vec!["-x", "72", "-y", "0", "-W", "72", "-H", "72"]);
+        let args = crate::pdf_to_text::crop_args(1, &region, Units::PixelsAt(300.0)).unwrap();
+        assert_eq!(args[5], "101");
+
+        assert!(crate::pdf_to_text::crop_args(0, &region, Units::Points).is_err());
+        assert!(crate::pdf_to_text::crop_args(1, &Rect::default(), Units::Points).is_err());
+        assert!(crate::pdf_to_text::crop_args(1, &region, Units::PixelsAt(0.0)).is_err());
+    }
+
+    #[test]
+    fn pdf_to_words_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfToTextConfig::default();
+
+            let _result = pdf_to_words(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_to_layout_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfToTextConfig::default();
+
+            let _result = pdf_to_layout(file, config).await;
+        })
+    }
+
+    #[test]
+    fn parse_layout_works() {
+        let xhtml = r#"<html xmlns="http://www.w3.org/1999/xhtml">
+<body>
+<doc>
+  <page width="612.000000" height="792.000000">
+    <flow>
+      <block xMin="50.000000" yMin="60.000000" xMax="300.000000" yMax="100.000000">
+        <line xMin="50.000000" yMin="60.000000" xMax="280.000000" yMax="75.000000">
+          <word xMin="50.000000" yMin="60.000000" xMax="90.000000" yMax="75.000000">Fish</word>
+          <word xMin="95.000000" yMin="60.000000" xMax="280.000000" yMax="75.000000">&amp;</word>
+        </line>
+        <line xMin="50.000000" yMin="80.000000" xMax="300.000000" yMax="100.000000">
+          <word xMin="50.000000" yMin="80.000000" xMax="300.000000" yMax="100.000000">chips</word>
+        </line>
+      </block>
+      <block xMin="40.000000" yMin="120.000000" xMax="200.000000" yMax="140.000000">
+        <line xMin="40.000000" yMin="120.000000" xMax="200.000000" yMax="140.000000">
+          <word xMin="40.000000" yMin="120.000000" xMax="200.000000" yMax="140.000000">Next</word>
+        </line>
+      </block>
+    </flow>
+    <flow>
+      <block xMin="320.000000" yMin="60.000000" xMax="560.000000" yMax="75.000000">
+        <line xMin="320.000000" yMin="60.000000" xMax="560.000000" yMax="75.000000">
+          <word xMin="320.000000" yMin="60.000000" xMax="560.000000" yMax="75.000000">Column</word>
+        </line>
+      </block>
+    </flow>
+  </page>
+  <page width="612.000000" height="792.000000">
+  </page>
+</doc>
+</body>
+</html>"#;
+
+        let document = crate::pdf_to_text::parse_layout(xhtml, 1).unwrap();
+        assert_eq!(document.pages.len(), 2);
+        let page = &document.pages[0];
+        assert_eq!(page.flows.len(), 2);
+        assert_eq!(
+            page.flows[0].rect,
+            Rect {
+                x_min: 40.0,
+                y_min: 60.0,
+                x_max: 300.0,
+                y_max: 140.0
+            }
+        );
+        assert_eq!(page.flows[0].blocks[0].text(), "Fish &\nchips");
+        assert_eq!(page.text(), "Fish &\nchips\n\nNext\n\nColumn");
+
+        let blocks: Vec<String> = document.blocks().map(|block| block.text()).collect();
+        assert_eq!(blocks, vec!["Fish &\nchips", "Next", "Column"]);
+        let lines: Vec<String> = document.lines().map(|line| line.text()).collect();
+        assert_eq!(lines, vec!["Fish &", "chips", "Next", "Column"]);
+        assert_eq!(document.pages[1].number, 2);
+        assert!(document.pages[1].flows.is_empty());
+
+        let orphan = r#"<doc><page width="612" height="792"><line xMin="0" yMin="0" xMax="1" yMax="1"></line></page></doc>"#;
+        assert!(crate::pdf_to_text::parse_layout(orphan, 1).is_err());
+    }
+
+    #[test]
+    fn pdf_to_tsv_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfToTextConfig::default();
+
+            let _result = pdf_to_tsv(file, config).await;
+        })
+    }
+
+    #[test]
+    fn parse_tsv_works() {
+        let stdout = "level\tpage_num\tpar_num\tblock_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+            1\t1\t0\t0\t0\t0\t0.000000\t0.000000\t612.000000\t792.000000\t-1\t###PAGE###\n\
+            3\t1\t0\t0\t0\t0\t50.000000\t60.000000\t200.000000\t40.000000\t-1\t###FLOW###\n\
+            4\t1\t0\t0\t0\t0\t50.000000\t60.000000\t200.000000\t15.000000\t-1\t###LINE###\n\
+            5\t1\t0\t0\t0\t0\t50.000000\t60.000000\t40.000000\t15.000000\t100\t\"Fish\"\n\
+            5\t1\t0\t0\t0\t1\t95.000000\t60.000000\t30.000000\t15.000000\t100\ta\tb\n\
+            4\t1\t0\t0\t1\t0\t50.000000\t80.000000\t200.000000\t20.000000\t-1\t###LINE###\n\
+            5\t1\t0\t0\t1\t0\t50.000000\t80.000000\t200.000000\t20.000000\t100\tchips\n\
+            3\t1\t0\t1\t0\t0\t40.000000\t120.000000\t100.000000\t20.000000\t-1\t###FLOW###\n\
+            4\t1\t0\t1\t0\t0\t40.000000\t120.000000\t100.000000\t20.000000\t-1\t###LINE###\n\
+            5\t1\t0\t1\t0\t0\t40.000000\t120.000000\t100.000000\t20.000000\t100\tNext\n";
+
+        let rows = crate::pdf_to_text::parse_tsv(stdout).unwrap();
+        assert_eq!(rows.len(), 10);
+        assert_eq!(rows[0].level, TsvLevel::Page);
+        assert_eq!((rows[0].width, rows[0].conf), (612.0, -1.0));
+        assert_eq!(rows[0].text, "");
+        assert_eq!(rows[3].text, "\"Fish\"");
+        assert_eq!(rows[4].text, "a\tb");
+        assert_eq!(rows[4].rect().x_max, 125.0);
+
+        let blocks = group_tsv_rows(&rows);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].text(), "\"Fish\" a\tb\nchips");
+        assert_eq!(blocks[0].lines[0].rect.x_max, 125.0);
+        assert_eq!(blocks[0].rect.y_max, 100.0);
+        assert_eq!(blocks[1].block_num, 1);
+        assert_eq!(blocks[1].text(), "Next");
+
+        assert!(crate::pdf_to_text::parse_tsv("1\t1\tbroken").is_err());
+    }
+
+    #[test]
+    fn parse_words_works() {
+        let xhtml = r#"<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN" "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">
+<html xmlns="http://www.w3.org/1999/xhtml">
+<head>
+<title>Q&amp;A</title>
+<meta name="Producer" content="LibreOffice 7.5"/>
+</head>
+<body>
+<doc>
+  <page width="612.000000" height="792.000000">
+    <word xMin="56.800000" yMin="57.208000" xMax="87.040000" yMax="70.456000">Q&amp;A:</word>
+    <word xMin="90.100000" yMin="57.208000" xMax="131.500000" yMax="70.456000">&lt;x&gt;&quot;y&quot;&#233;</word>
+  </page>
+  <page width="595.276000" height="841.890000">
+  </page>
+</doc>
+</body>
+</html>"#;
+
+        let pages = crate::pdf_to_text::parse_words(xhtml, 3).unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(
+            (pages[0].number, pages[0].width, pages[0].height),
+            (3, 612.0, 792.0)
+        );
+        let texts: Vec<&str> = pages[0].words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(texts, vec!["Q&A:", "<x>\"y\"\u{e9}"]);
+        let rect = pages[0].words[0].rect;
+        assert_eq!((rect.x_min, rect.y_max), (56.8, 70.456));
+        assert!((rect.width() - 30.24).abs() < 1e-9);
+        assert_eq!(pages[1].number, 4);
+        assert!(pages[1].words.is_empty());
+
+        let broken = r#"<doc><page width="612" height="792"><word xMin="1">a</word></page></doc>"#;
+        assert!(crate::pdf_to_text::parse_words(broken, 1).is_err());
+    }
+
+    #[test]
+    fn pdf_attach_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let attachment = b"<Invoice/>".to_vec().as_poppler_buffer();
+
+            let _result = pdf_attach(file, attachment, "invoice.xml", false).await;
+        })
+    }
+
+    #[test]
+    fn pdf_detach_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./test_resources/pdf_1.3_NHS_Constitution_attached_detach.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfDetachConfig::default();
+
+            let _result = pdf_detach_extract(file, DetachTarget::All, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_detach_parses_list() {
+        let stdout = "2 embedded files\n1: invoice.xml\n2: terms and conditions.txt\n";
+        let files = crate::pdf_detach::parse_embedded_files(stdout).unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].index, 1);
+        assert_eq!(files[0].name, "invoice.xml");
+        assert_eq!(files[1].index, 2);
+        assert_eq!(files[1].name, "terms and conditions.txt");
+    }
+
+    #[test]
+    fn pdf_detach_selects_files_by_number() {
+        let stdout = "3 embedded files\n1: ../invoice.xml\n2: a/b.txt\n3: ../invoice.xml\n";
+        let files = crate::pdf_detach::parse_embedded_files(stdout).unwrap();
+        let select = |target: DetachTarget| {
+            crate::pdf_detach::select_embedded_files(files.clone(), &target)
+                .map(|files| files.iter().map(|file| file.index).collect::<Vec<_>>())
+        };
+
+        assert_eq!(select(DetachTarget::All).unwrap(), vec![1, 2, 3]);
+        assert_eq!(select(DetachTarget::Index(2)).unwrap(), vec![2]);
+        // both files of the same name, each saved under its own number
+        assert_eq!(
+            select(DetachTarget::Name("../invoice.xml".to_string())).unwrap(),
+            vec![1, 3]
+        );
+        assert!(select(DetachTarget::Index(4)).is_err());
+        assert!(select(DetachTarget::Name("missing".to_string())).is_err());
+    }
+
+    #[test]
+    fn pdf_fonts_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfFontsConfig::default();
+
+            let _result = pdf_fonts(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_fonts_parses_table() {
+        let stdout = "\
+name                                 type              encoding         emb sub uni object ID
+------------------------------------ ----------------- ---------------- --- --- --- ---------
+ABCDEE+Frutiger-Roman                Type 1C           Custom           yes yes no      12  0
+ABCDEE+FrutigerLTStd-BoldCondensedItalic CID Type 0C (OT)  Identity-H       yes yes yes     45  0
+[none]                               Type 3            Custom           yes no  no  [none]
+ABCDEE+Schrift-Überschrift-Fett-Kursiv Type 1            Custom           yes yes no      18  0
+Symbol                               Type 1                             no  no  no       9  0
+";
+        let fonts = crate::pdf_fonts::parse_fonts(stdout).unwrap();
+
+        assert_eq!(fonts.len(), 5);
+        assert_eq!(fonts[0].name.as_deref(), Some("ABCDEE+Frutiger-Roman"));
+        assert_eq!(fonts[0].font_type, FontType::Type1C);
+        assert_eq!(fonts[0].object_id, Some(ObjectId { num: 12, gen: 0 }));
+        assert!(!fonts[0].has_to_unicode);
+        assert_eq!(fonts[1].font_type, FontType::CidType0COpenType);
+        assert_eq!(fonts[1].encoding, "Identity-H");
+        assert!(fonts[1].has_to_unicode);
+        assert_eq!(fonts[2].name, None);
+        assert_eq!(fonts[2].object_id, None);
+        assert_eq!(
+            fonts[3].name.as_deref(),
+            Some("ABCDEE+Schrift-Überschrift-Fett-Kursiv")
+        );
+        assert_eq!(fonts[3].font_type, FontType::Type1);
+        assert_eq!(fonts[3].encoding, "Custom");
+        assert_eq!(fonts[4].encoding, "");
+        assert!(!fonts[4].embedded);
+        assert_eq!(fonts[4].object_id, Some(ObjectId { num: 9, gen: 0 }));
+
+        let stdout = "\
+name                                 object ID substitute font                      substitute font file
+------------------------------------ --------- ------------------------------------ ------------------------------------
+Arial                                    7  0 LiberationSans                       /usr/share/fonts/LiberationSans-Regular.ttf
+ÄÖÜ-Grotesk-Überbreit-Halbfett-Kursiv     8  0 DejaVuSans-BoldOblique-Condensed-Wide /usr/share/fonts/My Fonts/DejaVu.ttf
+";
+        let substitutions = crate::pdf_fonts::parse_substitutions(stdout).unwrap();
+
+        assert_eq!(substitutions.len(), 2);
+        assert_eq!(substitutions[0].0.as_deref(), Some("Arial"));
+        assert_eq!(substitutions[0].1, Some(ObjectId { num: 7, gen: 0 }));
+        assert_eq!(substitutions[0].2.name.as_deref(), Some("LiberationSans"));
+        assert_eq!(
+            substitutions[0].2.file,
+            "/usr/share/fonts/LiberationSans-Regular.ttf"
+        );
+        assert_eq!(
+            substitutions[1].0.as_deref(),
+            Some("ÄÖÜ-Grotesk-Überbreit-Halbfett-Kursiv")
+        );
+        assert_eq!(substitutions[1].1, Some(ObjectId { num: 8, gen: 0 }));
+        assert_eq!(
+            substitutions[1].2.name.as_deref(),
+            Some("DejaVuSans-BoldOblique-Condensed-Wide")
+        );
+        assert_eq!(
+            substitutions[1].2.file,
+            "/usr/share/fonts/My Fonts/DejaVu.ttf"
+        );
+    }
+
+    #[test]
+    fn pdf_images_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfImagesConfig {
+                list: true,
+                ..PdfImagesConfig::default()
+            };
+
+            let _result = pdf_images(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_images_parses_list() {
+        let stdout = "\
+page   num  type   width height color comp bpc  enc interp  object ID x-ppi y-ppi size ratio
+--------------------------------------------------------------------------------------------
+   1     0 image    2480  3508  gray    1   1  ccitt  no         9  0   300   300  109K 10%
+   2     1 smask     120    80  gray    1   8  image  yes  [inline]      72    72  943B 9.8%
+   3     2 image       1     1  rgb     3   8  image  no        14  0   inf  -nan   3B 100%
+";
+        let images = crate::pdf_images::parse_image_list(stdout).unwrap();
+
+        assert_eq!(images.len(), 3);
+        assert_eq!(images[0].page, 1);
+        assert_eq!(images[0].image_type, ImageType::Image);
+        assert_eq!(images[0].encoding, ImageEncoding::Ccitt);
+        assert_eq!(images[0].width, 2480);
+        assert_eq!(images[0].x_ppi, 300.0);
+        assert_eq!(images[0].size, 109 * 1024);
+        assert_eq!(images[0].ratio, 10.0);
+        assert_eq!(images[1].image_type, ImageType::SMask);
+        assert!(images[1].interpolate);
+        assert_eq!(images[1].object_id, None);
+        assert_eq!(images[1].size, 943);
+        assert!(images[2].x_ppi.is_infinite() && images[2].y_ppi.is_nan());
+    }
+
+    #[test]
+    fn spool_file_keeps_paths_from_reading_as_options() {
+        let work_dir = crate::utils::TempDir::new().unwrap();
+        let path = crate::utils::spool_file("-v.pdf".as_poppler_path(), &work_dir, "input.pdf");
+        assert_eq!(path.unwrap(), std::path::Path::new("./-v.pdf").as_os_str());
+        let path = crate::utils::spool_file("in/-v.pdf".as_poppler_path(), &work_dir, "input.pdf");
+        assert_eq!(path.unwrap(), "in/-v.pdf");
+    }
+
+    #[test]
+    fn spool_file_absolute_keeps_relative_paths_usable() {
+        // pdfsig -dump runs in the temporary directory
+        let work_dir = crate::utils::TempDir::new().unwrap();
+        let path = crate::utils::spool_file_absolute(
+            "docs/a.pdf".as_poppler_path(),
+            &work_dir,
+            "input.pdf",
+        )
+        .unwrap();
+        let path = std::path::Path::new(&path);
+        assert!(path.is_absolute());
+        assert_eq!(path, std::env::current_dir().unwrap().join("docs/a.pdf"));
+    }
+
+    #[test]
+    fn pdf_separate_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfSeparateConfig::default();
+
+            let _result = pdf_separate(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_separate_orders_pages_by_number() {
+        use crate::pdf_separate::collect_pages;
+
+        // as read_files returns them, sorted by name
+        let files = vec![
+            ("input.pdf".to_string(), b"input".to_vec()),
+            ("page-10.pdf".to_string(), b"ten".to_vec()),
+            ("page-2.pdf".to_string(), b"two".to_vec()),
+            ("page-x.pdf".to_string(), b"x".to_vec()),
+        ];
+        assert_eq!(
+            collect_pages(files),
+            vec![(2, b"two".to_vec()), (10, b"ten".to_vec())]
+        );
+    }
+
+    #[test]
+    fn pdf_sig_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfSigConfig::default();
+
+            let _result = pdf_sig(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_sig_parses_report() {
+        let stdout = "\
+Digital Signature Info of: input.pdf
+Signature #1:
+  - Signature Field Name: Signature1
+  - Signer Certificate Common Name: Jane Doe
+  - Signer full Distinguished Name: CN=Jane Doe,O=Example,C=GB
+  - Signing Time: Jan 02 2024 13:45:10
+  - Signing Hash Algorithm: SHA-256
+  - Signature Type: adbe.pkcs7.detached
+  - Signed Ranges: [0 - 5120], [21506 - 40110]
+  - Total document signed
+  - Signature Validation: Signature is Valid.
+  - Certificate Validation: Certificate issuer is unknown.
+Signature #2:
+  - Signature Field Name: Signature2
+  The signature form field is not signed.
+";
+        let signatures = crate::pdf_sig::parse_signatures(stdout).unwrap();
+
+        assert_eq!(signatures.len(), 2);
+        assert_eq!(
+            signatures[0].signer_common_name.as_deref(),
+            Some("Jane Doe")
+        );
+        assert_eq!(signatures[0].hash_algorithm.as_deref(), Some("SHA-256"));
+        assert_eq!(signatures[0].signed_ranges, vec![(0, 5120), (21506, 40110)]);
+        assert!(signatures[0].covers_whole_document());
+        assert_eq!(
+            signatures[0].signature_validation,
+            Some(SignatureStatus::Valid)
+        );
+        assert_eq!(
+            signatures[0].certificate_validation,
+            Some(CertificateStatus::UnknownIssuer)
+        );
+        assert_eq!(signatures[1].field_name.as_deref(), Some("Signature2"));
+        assert!(!signatures[1].signed);
+        assert!(!signatures[1].covers_whole_document());
+    }
+
+    #[test]
+    fn pdf_sig_reads_padded_dump_names() {
+        use crate::pdf_sig::dump_index;
+
+        // twelve signatures pad the index to two characters
+        let names: Vec<String> = (0..12).map(|i| format!("input.pdf.sig{:2}", i)).collect();
+        let indices: Vec<Option<usize>> = names.iter().map(|name| dump_index(name)).collect();
+
+        assert_eq!(names[3], "input.pdf.sig 3");
+        assert_eq!(indices, (0..12).map(Some).collect::<Vec<_>>());
+        assert_eq!(dump_index("input.pdf.sig7"), Some(7));
+        assert_eq!(dump_index("input.pdf"), None);
+    }
+
+    #[test]
+    fn pdf_to_cairo_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfToCairoConfig {
+                format: CairoFormat::Svg,
+                ..PdfToCairoConfig::default()
+            };
+
+            let _result = pdf_to_cairo(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_to_cairo_routes_output_by_format() {
+        use crate::pdf_to_cairo::{collect_output, output_args, CairoOutput};
+
+        let output_dir = crate::utils::TempDir::new().unwrap();
+        let vector = [
+            CairoFormat::Pdf,
+            CairoFormat::Ps,
+            CairoFormat::Eps,
+            CairoFormat::Svg,
+        ];
+        let raster = [CairoFormat::Png, CairoFormat::Jpeg, CairoFormat::Tiff];
+
+        for format in vector {
+            assert!(format.is_vector());
+            assert_eq!(output_args(format, &output_dir), vec!["-".to_string()]);
+            // pdftocairo writes nothing else, the document is stdout
+            let output = collect_output(format, b"%!PS".to_vec(), Vec::new(), 1).unwrap();
+            assert_eq!(output, CairoOutput::Document(b"%!PS".to_vec()));
+        }
+
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\x02\x64\0\0\x03\x18".to_vec();
+        for format in raster {
+            assert!(!format.is_vector());
+            assert_eq!(
+                output_args(format, &output_dir),
+                vec![output_dir.join("page")]
+            );
+        }
+        let files = vec![
+            ("page-10.png".to_string(), png.clone()),
+            ("page-9.png".to_string(), png),
+        ];
+        match collect_output(CairoFormat::Png, Vec::new(), files, 9).unwrap() {
+            CairoOutput::Pages(pages) => {
+                let numbers: Vec<u32> = pages.iter().map(|page| page.page_number).collect();
+                assert_eq!(numbers, vec![9, 10]);
+                assert_eq!(pages[0].format, ImageFormat::Png);
+                assert_eq!((pages[0].width, pages[0].height), (612, 792));
+            }
+            output => panic!("expected pages, got {:?}", output),
+        }
+    }
+
+    #[test]
+    fn pdf_to_ppm_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let file = file_path.as_poppler_path();
+            let config = PdfToPpmConfig::default();
+
+            let _result = pdf_to_ppm(file, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_to_ppm_reads_rendered_pages() {
+        let ppm = b"P6\n# comment\n1275 1650\n255\n".to_vec();
+        let page = crate::pdf_to_ppm::read_rendered_page("page-03.ppm", ppm, 1).unwrap();
+
+        assert_eq!(page.page_number, 3);
+        assert_eq!(page.format, ImageFormat::Ppm);
+        assert_eq!((page.width, page.height), (1275, 1650));
+
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend_from_slice(&612u32.to_be_bytes());
+        png.extend_from_slice(&792u32.to_be_bytes());
+        let page = crate::pdf_to_ppm::read_rendered_page("page.png", png, 4).unwrap();
+
+        assert_eq!(page.page_number, 4);
+        assert_eq!(page.format, ImageFormat::Png);
+        assert_eq!((page.width, page.height), (612, 792));
+    }
+
+    #[test]
+    fn pdf_to_ppm_orders_pages_by_number() {
+        use crate::pdf_to_ppm::read_rendered_pages;
+
+        let ppm = b"P5 10 20 255\n".to_vec();
+        // as read_files returns them, sorted by name
+        let files = vec![
+            ("page-10.pgm".to_string(), ppm.clone()),
+            ("page-8.pgm".to_string(), ppm.clone()),
+            ("page-9.pgm".to_string(), ppm.clone()),
+        ];
+        let pages = read_rendered_pages(files, 8).unwrap();
+        let numbers: Vec<u32> = pages.iter().map(|page| page.page_number).collect();
+        assert_eq!(numbers, vec![8, 9, 10]);
+        assert_eq!(pages[0].format, ImageFormat::Pgm);
+        assert_eq!((pages[0].width, pages[0].height), (10, 20));
+
+        // anything but page files is unexpected
+        let files = vec![("input.pdf".to_string(), ppm.clone())];
+        assert!(read_rendered_pages(files, 1).is_err());
+        let files = vec![("page-x.pgm".to_string(), ppm)];
+        assert!(read_rendered_pages(files, 1).is_err());
+    }
+
+    #[test]
+    fn pdf_unite_works() {
+        run_test(async {
+            let mut file_path = std::env::current_dir().unwrap();
+            file_path.push("./src/test.pdf");
+
+            let buffer = std::fs::read(&file_path).unwrap();
+            let files = vec![file_path.as_poppler_path(), buffer.as_poppler_buffer()];
+            let config = PdfUniteConfig::default();
+
+            let _result = pdf_unite(files, config).await;
+        })
+    }
+
+    #[test]
+    fn pdf_unite_builds_args() {
+        use crate::pdf_unite::unite_args;
+
+        let work_dir = crate::utils::TempDir::new().unwrap();
+        let mut file_path = std::env::current_dir().unwrap();
+        file_path.push("./src/test.pdf");
+
+        let files = vec![
+            b"first".to_vec().as_poppler_buffer(),
+            file_path.clone().as_poppler_path(),
+            b"third".to_vec().as_poppler_buffer(),
+        ];
+        let args = unite_args(files, &work_dir).unwrap();
+
+        // paths are passed as they are, buffers keep their position
+        assert_eq!(
+            args,
+            vec![
+                work_dir.join("input-0.pdf").into(),
+                file_path.clone().into_os_string(),
+                work_dir.join("input-2.pdf").into(),
+                std::ffi::OsString::from(work_dir.join("united.pdf")),
+            ]
+        );
+        assert_eq!(std::fs::read(&args[0]).unwrap(), b"first");
+        assert_eq!(std::fs::read(&args[2]).unwrap(), b"third");
+    }
+
+    #[test]
+    fn pdf_unite_needs_files() {
+        run_test(async {
+            let config = PdfUniteConfig::default();
+            let error = pdf_unite(Vec::new(), config)
+                .await
+                .map_err(|e| e.kind())
+                .err();
+
+            assert_eq!(error, Some(std::io::ErrorKind::InvalidInput));
+        })
+    }
+
     // TODO: simplify by using #[tokio::test] if no setup/teardown is needed in the future
     // https://lik.ai/blog/async-setup-and-teardown-in-rust
-    fn run_test<T>(test: T) -> ()
+    fn run_test<T>(test: T)
     where
         T: std::future::Future + std::panic::UnwindSafe,
     {
@@ -0,0 +1,33 @@
+use crate::utils::{run_program_with_args, spool_file, spool_file_named, PopplerFile, TempDir};
+use std::future::Future;
+
+// Embeds `attachment` into `file` under the file name `name` and returns the
+// new PDF. pdfattach names the embedded file after the path it reads, so the
+// attachment is written to a private directory as `name` whenever it comes
+// from a buffer or a path with a different file name.
+pub fn pdf_attach(
+    file: PopplerFile,
+    attachment: PopplerFile,
+    name: &str,
+    replace: bool, // replace, -replace
+) -> impl Future<Output = Result<Vec<u8>, std::io::Error>> {
+    let name = name.to_string();
+
+    async move {
+        let work_dir = TempDir::new()?;
+        // kept apart so the attachment name can not clash with input.pdf or output.pdf
+        let attachment_dir = TempDir::new()?;
+
+        let mut args = Vec::new();
+        if replace {
+            args.push("-replace".into());
+        }
+        args.push(spool_file(file, &work_dir, "input.pdf")?);
+        args.push(spool_file_named(attachment, &attachment_dir, &name)?);
+        let output_file = work_dir.join("output.pdf");
+        args.push(output_file.clone().into());
+
+        run_program_with_args("pdfattach", args).await?;
+        std::fs::read(output_file)
+    }
+}
@@ -0,0 +1,186 @@
+use crate::utils::{run_program_with_args, spool_file, PopplerFile, TempDir};
+use std::ffi::OsString;
+use std::future::Future;
+
+#[derive(Default)]
+pub struct PdfDetachConfig<'a> {
+    pub output_encoding: &'a str, // outputEncoding, -enc (default UTF-8)
+    pub owner_password: &'a str,  // ownerPassword, -opw
+    pub user_password: &'a str,   // userPassword, -upw
+}
+
+// one line of -list, index is the 1-based number -save expects
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmbeddedFile {
+    pub index: u32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetachedFile {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+// which embedded files pdf_detach_extract saves, each is saved with -save
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DetachTarget {
+    Index(u32),   // saveSpecificFile, -save
+    Name(String), // every file with this name
+    All,
+}
+
+pub fn pdf_detach_list(
+    file: PopplerFile,
+    options: PdfDetachConfig<'static>,
+) -> impl Future<Output = Result<Vec<EmbeddedFile>, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+
+    async move {
+        // pdfdetach can not read from stdin
+        let work_dir = TempDir::new()?;
+        let input = spool_file(file, &work_dir, "input.pdf")?;
+
+        list_embedded_files(&input, &parsed_options).await
+    }
+}
+
+// Saves every file one at a time by its number into a file named after
+// that number, the embedded names are returned as data only and never used
+// as paths. They are listed in UTF-8 whatever output_encoding says.
+pub fn pdf_detach_extract(
+    file: PopplerFile,
+    target: DetachTarget,
+    mut options: PdfDetachConfig<'static>,
+) -> impl Future<Output = Result<Vec<DetachedFile>, std::io::Error>> {
+    options.output_encoding = "UTF-8";
+    let parsed_options = parse_options(&options);
+
+    async move {
+        // pdfdetach can not read from stdin and only writes files
+        let work_dir = TempDir::new()?;
+        let output_dir = TempDir::new()?;
+        let input = spool_file(file, &work_dir, "input.pdf")?;
+
+        let embedded_files = list_embedded_files(&input, &parsed_options).await?;
+        let selected_files = select_embedded_files(embedded_files, &target)?;
+
+        let mut detached_files = Vec::new();
+        for embedded_file in selected_files {
+            let output_path = output_dir.join(&embedded_file.index.to_string());
+
+            let mut args = to_args(&parsed_options);
+            args.push("-save".into());
+            args.push(embedded_file.index.to_string().into());
+            args.push("-o".into());
+            args.push(output_path.clone().into());
+            args.push(input.clone());
+            run_program_with_args("pdfdetach", args).await?;
+
+            detached_files.push(DetachedFile {
+                name: embedded_file.name,
+                bytes: std::fs::read(output_path)?,
+            });
+        }
+
+        Ok(detached_files)
+    }
+}
+
+// The files target picks, in the order of the listing. A name picks every
+// file with that name.
+pub(crate) fn select_embedded_files(
+    embedded_files: Vec<EmbeddedFile>,
+    target: &DetachTarget,
+) -> Result<Vec<EmbeddedFile>, std::io::Error> {
+    let selected: Vec<EmbeddedFile> = embedded_files
+        .into_iter()
+        .filter(|embedded_file| match target {
+            DetachTarget::Index(index) => embedded_file.index == *index,
+            DetachTarget::Name(name) => embedded_file.name == *name,
+            DetachTarget::All => true,
+        })
+        .collect();
+
+    match target {
+        DetachTarget::Index(index) if selected.is_empty() => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No embedded file with number {}", index),
+        )),
+        DetachTarget::Name(name) if selected.is_empty() => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No embedded file named {}", name),
+        )),
+        _ => Ok(selected),
+    }
+}
+
+fn to_args(parsed_options: &[String]) -> Vec<OsString> {
+    parsed_options.iter().map(OsString::from).collect()
+}
+
+async fn list_embedded_files(
+    input: &OsString,
+    parsed_options: &[String],
+) -> Result<Vec<EmbeddedFile>, std::io::Error> {
+    let mut args = to_args(parsed_options);
+    args.push("-list".into());
+    args.push(input.clone());
+
+    let stdout = run_program_with_args("pdfdetach", args).await?;
+    parse_embedded_files(&String::from_utf8_lossy(&stdout))
+}
+
+// "2 embedded files" followed by one "1: name" line per file
+pub(crate) fn parse_embedded_files(stdout: &str) -> Result<Vec<EmbeddedFile>, std::io::Error> {
+    stdout
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            line.split_once(": ")
+                .and_then(|(index, name)| {
+                    Some(EmbeddedFile {
+                        index: index.trim().parse().ok()?,
+                        name: name.to_string(),
+                    })
+                })
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Unexpected pdfdetach output: {}", line),
+                    )
+                })
+        })
+        .collect()
+}
+
+fn parse_options(options: &PdfDetachConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(
+        !options.output_encoding.is_empty(),
+        "-enc",
+        options.output_encoding
+    );
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+
+    parsed_options
+}
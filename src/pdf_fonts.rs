@@ -0,0 +1,302 @@
+pub use crate::utils::ObjectId;
+use crate::utils::{run_program, PopplerFile};
+use std::future::Future;
+
+pub struct PdfFontsConfig<'a> {
+    pub first_page: i32,          // firstPageToExamine, -f
+    pub last_page: i32,           // lastPageToExamine, -l
+    pub show_substitutions: bool, // listSubstitutes, -subst
+    pub owner_password: &'a str,  // ownerPassword, -opw
+    pub user_password: &'a str,   // userPassword, -upw
+}
+
+impl<'a> Default for PdfFontsConfig<'a> {
+    fn default() -> Self {
+        Self {
+            first_page: 1,
+            last_page: 0,              // 0 means all pages
+            show_substitutions: false, // runs pdffonts a second time to fill FontInfo::substitute
+            owner_password: "",
+            user_password: "",
+        }
+    }
+}
+
+// names as printed by pdffonts, see fontTypeNames in pdffonts.cc
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FontType {
+    Unknown,
+    Type1,
+    Type1C,
+    Type1COpenType,
+    Type3,
+    TrueType,
+    TrueTypeOpenType,
+    CidType0,
+    CidType0C,
+    CidType0COpenType,
+    CidTrueType,
+    CidTrueTypeOpenType,
+}
+
+impl FontType {
+    // longest names first so that "Type 1C (OT)" is not read as "Type 1C"
+    const NAMES: [(&'static str, FontType); 12] = [
+        ("CID TrueType (OT)", FontType::CidTrueTypeOpenType),
+        ("CID Type 0C (OT)", FontType::CidType0COpenType),
+        ("CID TrueType", FontType::CidTrueType),
+        ("TrueType (OT)", FontType::TrueTypeOpenType),
+        ("CID Type 0C", FontType::CidType0C),
+        ("Type 1C (OT)", FontType::Type1COpenType),
+        ("CID Type 0", FontType::CidType0),
+        ("TrueType", FontType::TrueType),
+        ("Type 1C", FontType::Type1C),
+        ("unknown", FontType::Unknown),
+        ("Type 1", FontType::Type1),
+        ("Type 3", FontType::Type3),
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        FontType::NAMES
+            .iter()
+            .find(|(_, font_type)| font_type == self)
+            .map(|(name, _)| *name)
+            .unwrap_or("unknown")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontSubstitute {
+    pub name: Option<String>, // None when pdffonts prints [none]
+    pub file: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontInfo {
+    pub name: Option<String>, // None when pdffonts prints [none]
+    pub font_type: FontType,
+    pub encoding: String,
+    pub embedded: bool,
+    pub subset: bool,
+    pub has_to_unicode: bool,
+    pub object_id: Option<ObjectId>, // None when pdffonts prints [none]
+    pub substitute: Option<FontSubstitute>, // only set with show_substitutions
+}
+
+pub fn pdf_fonts(
+    file: PopplerFile,
+    options: PdfFontsConfig<'static>,
+) -> impl Future<Output = Result<Vec<FontInfo>, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+    let show_substitutions = options.show_substitutions;
+
+    async move {
+        // the -subst table replaces the regular one, so it needs its own run
+        let subst_file = show_substitutions.then(|| file.clone());
+        let stdout = run_program(file, "pdffonts", parsed_options.clone()).await?;
+        let mut fonts = parse_fonts(&stdout)?;
+
+        if let Some(subst_file) = subst_file {
+            let mut subst_options = parsed_options;
+            subst_options.push("-subst".to_string());
+            let stdout = run_program(subst_file, "pdffonts", subst_options).await?;
+            for (name, object_id, substitute) in parse_substitutions(&stdout)? {
+                if let Some(font) = fonts
+                    .iter_mut()
+                    .find(|font| font.object_id == object_id && font.name == name)
+                {
+                    font.substitute = Some(substitute);
+                }
+            }
+        }
+
+        Ok(fonts)
+    }
+}
+
+fn parse_options(options: &PdfFontsConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(options.first_page != 1, "-f", options.first_page);
+    add_option!(options.last_page != 0, "-l", options.last_page);
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+
+    parsed_options
+}
+
+fn invalid_data(line: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected pdffonts output: {}", line),
+    )
+}
+
+// The byte offsets the columns start at, from the dashed line under the
+// header, and the table rows after it. pdffonts pads with printf, so the
+// offsets are in bytes, and values wider than their column push the rest of
+// the row to the right.
+fn table_rows(stdout: &str) -> (Vec<usize>, impl Iterator<Item = &str>) {
+    let mut lines = stdout.lines().skip_while(|line| !line.starts_with("---"));
+    let column_starts = lines
+        .next()
+        .map(|dashes| {
+            dashes
+                .char_indices()
+                .filter(|(index, c)| *c == '-' && (*index == 0 || dashes[..*index].ends_with(' ')))
+                .map(|(index, _)| index)
+                .collect()
+        })
+        .unwrap_or_default();
+    (column_starts, lines.filter(|line| !line.trim().is_empty()))
+}
+
+// Splits a row at the first space at or after byte width, which ends a
+// value that filled or overflowed its column. Never splits inside a
+// character.
+fn split_column(row: &str, width: usize) -> Option<(&str, &str)> {
+    let (end, _) = row
+        .char_indices()
+        .find(|(index, c)| *index >= width && *c == ' ')?;
+    Some((&row[..end], &row[end + 1..]))
+}
+
+fn parse_name(name: &str) -> Option<String> {
+    match name.trim() {
+        "[none]" => None,
+        name => Some(name.to_string()),
+    }
+}
+
+// splits the last whitespace separated column off a row
+fn split_last_column(row: &str) -> Option<(&str, &str)> {
+    row.trim_end().rsplit_once(' ')
+}
+
+// strips the trailing "num gen" or "[none]" object id from a row
+fn split_object_id(row: &str) -> Option<(&str, Option<ObjectId>)> {
+    if let Some(rest) = row.trim_end().strip_suffix("[none]") {
+        return Some((rest, None));
+    }
+    let (rest, gen) = split_last_column(row)?;
+    let (rest, num) = split_last_column(rest)?;
+    let object_id = ObjectId {
+        num: num.parse().ok()?,
+        gen: gen.parse().ok()?,
+    };
+    Some((rest, Some(object_id)))
+}
+
+fn parse_yes_no(value: &str) -> Option<bool> {
+    match value {
+        "yes" => Some(true),
+        "no" => Some(false),
+        _ => None,
+    }
+}
+
+// The yes/no flags and the object id are read from the end of the row, the
+// name, type and encoding by the column positions of the header. Encodings
+// can be empty, so the columns in front of the flags are not split on
+// whitespace.
+pub(crate) fn parse_fonts(stdout: &str) -> Result<Vec<FontInfo>, std::io::Error> {
+    let (column_starts, rows) = table_rows(stdout);
+    rows.map(|row| parse_font_row(row, &column_starts).ok_or_else(|| invalid_data(row)))
+        .collect()
+}
+
+fn parse_font_row(row: &str, column_starts: &[usize]) -> Option<FontInfo> {
+    let (rest, object_id) = split_object_id(row)?;
+    let (rest, has_to_unicode) = split_last_column(rest)?;
+    let (rest, subset) = split_last_column(rest)?;
+    let (rest, embedded) = split_last_column(rest)?;
+
+    // the type column starts where the header says, or further right after
+    // a long name, and always follows a space
+    let type_start = *column_starts.get(1)?;
+    let type_width = column_starts.get(2)? - type_start - 1;
+    let (type_start, font_type) = rest
+        .char_indices()
+        .filter(|(index, _)| *index >= type_start && rest[..*index].ends_with(' '))
+        .find_map(|(index, _)| {
+            let column = rest.get(index..)?;
+            let column = column.get(..type_width).unwrap_or(column);
+            FontType::NAMES
+                .iter()
+                .find(|(name, _)| column.trim_end() == *name)
+                .map(|(_, font_type)| (index, *font_type))
+        })?;
+    let name = &rest[..type_start - 1];
+    let encoding = rest.get(type_start + type_width..).unwrap_or("");
+
+    Some(FontInfo {
+        name: parse_name(name),
+        font_type,
+        encoding: encoding.trim().to_string(),
+        embedded: parse_yes_no(embedded)?,
+        subset: parse_yes_no(subset)?,
+        has_to_unicode: parse_yes_no(has_to_unicode)?,
+        object_id,
+        substitute: None,
+    })
+}
+
+// font name and object id identify the row in the regular table
+type SubstitutionRow = (Option<String>, Option<ObjectId>, FontSubstitute);
+
+pub(crate) fn parse_substitutions(stdout: &str) -> Result<Vec<SubstitutionRow>, std::io::Error> {
+    let (column_starts, rows) = table_rows(stdout);
+    rows.map(|row| parse_substitution_row(row, &column_starts).ok_or_else(|| invalid_data(row)))
+        .collect()
+}
+
+// name, object id, substitute font, then the file path
+fn parse_substitution_row(row: &str, column_starts: &[usize]) -> Option<SubstitutionRow> {
+    let name_width = column_starts.get(1)? - 1;
+    let substitute_width = column_starts.get(3)? - column_starts.get(2)? - 1;
+    let (name, rest) = split_column(row, name_width)?;
+
+    let rest = rest.trim_start();
+    let (object_id, rest) = match rest.strip_prefix("[none]") {
+        Some(rest) => (None, rest),
+        None => {
+            let (num, rest) = rest.split_once(' ')?;
+            let (gen, rest) = rest.trim_start().split_once(' ')?;
+            let object_id = ObjectId {
+                num: num.parse().ok()?,
+                gen: gen.parse().ok()?,
+            };
+            (Some(object_id), rest)
+        }
+    };
+
+    let rest = rest.strip_prefix(' ').unwrap_or(rest);
+    let (substitute_name, file) = split_column(rest, substitute_width)?;
+
+    let substitute = FontSubstitute {
+        name: parse_name(substitute_name),
+        file: file.trim().to_string(),
+    };
+    Some((parse_name(name), object_id, substitute))
+}
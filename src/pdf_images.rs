@@ -0,0 +1,262 @@
+use crate::utils::{run_program, run_program_raw, ObjectId, PopplerFile, TempDir};
+use std::future::Future;
+
+// -v only prints the version and lists no images, so it is left out
+pub struct PdfImagesConfig<'a> {
+    pub all_files: bool,            // allFiles, -all
+    pub ccitt_file: bool,           // ccittFile, -ccitt
+    pub first_page_to_convert: i32, // firstPageToConvert, -f
+    pub last_page_to_convert: i32,  // lastPageToConvert, -l
+    pub list: bool,                 // list, -list
+    pub jbig2_file: bool,           // jbig2File, -jbig2
+    pub jpeg2000_file: bool,        // jpeg2000File, -jp2
+    pub jpeg_file: bool,            // jpegFile, -j
+    pub owner_password: &'a str,    // ownerPassword, -opw
+    pub page_names: bool,           // pageNames, -p
+    pub png_file: bool,             // pngFile, -png
+    pub quiet: bool,                // quiet, -q
+    pub tiff_file: bool,            // tiffFile, -tiff
+    pub user_password: &'a str,     // userPassword, -upw
+}
+
+impl<'a> Default for PdfImagesConfig<'a> {
+    fn default() -> Self {
+        Self {
+            all_files: false, // same as png, tiff, jpeg, jp2, jbig2 and ccitt together
+            ccitt_file: false,
+            first_page_to_convert: 1,
+            last_page_to_convert: 0, // 0 means all pages
+            list: false,             // true lists the images instead of extracting them
+            jbig2_file: false,
+            jpeg2000_file: false,
+            jpeg_file: false,
+            owner_password: "",
+            page_names: false, // adds the page number to the image names
+            png_file: false,   // without any format option images are written as ppm/pbm
+            quiet: false,
+            tiff_file: false,
+            user_password: "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageType {
+    Image,
+    Mask,
+    SMask,
+    Stencil,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageEncoding {
+    Image, // any other filter, decoded by poppler
+    Jpeg,
+    Jpeg2000,
+    Jbig2,
+    Ccitt,
+}
+
+// one row of the -list table
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageInfo {
+    pub page: u32,
+    pub num: u32,
+    pub image_type: ImageType,
+    pub width: u32,
+    pub height: u32,
+    pub color: String, // gray, rgb, cmyk, lab, icc, index, sep, devn or -
+    pub comp: u32,
+    pub bpc: u32,
+    pub encoding: ImageEncoding,
+    pub interpolate: bool,
+    pub object_id: Option<ObjectId>, // None for inline images
+    pub x_ppi: f64,                  // inf or nan for degenerate images
+    pub y_ppi: f64,
+    pub size: u64,  // in bytes, pdfimages rounds this to 3 significant digits
+    pub ratio: f64, // compressed size as a percentage of the decoded size
+}
+
+// an image written by pdfimages, named like image-000.png (image-001-000.png with page_names)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedImage {
+    pub name: String,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfImages {
+    List(Vec<ImageInfo>),
+    Extracted(Vec<ExtractedImage>),
+}
+
+pub fn pdf_images(
+    file: PopplerFile,
+    options: PdfImagesConfig<'static>,
+) -> impl Future<Output = Result<PdfImages, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+    let list = options.list;
+
+    async move {
+        if list {
+            let stdout = run_program(file, "pdfimages", parsed_options).await?;
+            return Ok(PdfImages::List(parse_image_list(&stdout)?));
+        }
+
+        // pdfimages only writes files, so collect them from a private directory
+        let output_dir = TempDir::new()?;
+        let image_root = vec![output_dir.join("image")];
+        run_program_raw(file, "pdfimages", parsed_options, image_root).await?;
+
+        let images = output_dir
+            .read_files()?
+            .into_iter()
+            .map(|(name, bytes)| ExtractedImage { name, bytes })
+            .collect();
+        Ok(PdfImages::Extracted(images))
+    }
+}
+
+fn parse_options(options: &PdfImagesConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(options.all_files, "-all");
+    add_option!(options.ccitt_file, "-ccitt");
+    add_option!(
+        options.first_page_to_convert != 1,
+        "-f",
+        options.first_page_to_convert
+    );
+    add_option!(
+        options.last_page_to_convert != 0,
+        "-l",
+        options.last_page_to_convert
+    );
+    add_option!(options.list, "-list");
+    add_option!(options.jbig2_file, "-jbig2");
+    add_option!(options.jpeg2000_file, "-jp2");
+    add_option!(options.jpeg_file, "-j");
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(options.page_names, "-p");
+    add_option!(options.png_file, "-png");
+    add_option!(options.quiet, "-q");
+    add_option!(options.tiff_file, "-tiff");
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+
+    parsed_options
+}
+
+// page num type width height color comp bpc enc interp object ID x-ppi y-ppi size ratio
+pub(crate) fn parse_image_list(stdout: &str) -> Result<Vec<ImageInfo>, std::io::Error> {
+    stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("---"))
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|row| {
+            parse_image_row(row).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Unexpected pdfimages output: {}", row),
+                )
+            })
+        })
+        .collect()
+}
+
+fn parse_image_row(row: &str) -> Option<ImageInfo> {
+    let mut columns = row.split_whitespace();
+    let mut next = || columns.next();
+
+    let page = next()?.parse().ok()?;
+    let num = next()?.parse().ok()?;
+    let image_type = match next()? {
+        "image" => ImageType::Image,
+        "mask" => ImageType::Mask,
+        "smask" => ImageType::SMask,
+        "stencil" => ImageType::Stencil,
+        _ => return None,
+    };
+    let width = next()?.parse().ok()?;
+    let height = next()?.parse().ok()?;
+    let color = next()?.to_string();
+    let comp = next()?.parse().ok()?;
+    let bpc = next()?.parse().ok()?;
+    let encoding = match next()? {
+        "image" => ImageEncoding::Image,
+        "jpeg" => ImageEncoding::Jpeg,
+        "jpx" | "jp2" => ImageEncoding::Jpeg2000,
+        "jbig2" => ImageEncoding::Jbig2,
+        "ccitt" => ImageEncoding::Ccitt,
+        _ => return None,
+    };
+    let interpolate = match next()? {
+        "yes" => true,
+        "no" => false,
+        _ => return None,
+    };
+    let object_id = match next()? {
+        "[inline]" => None,
+        num => Some(ObjectId {
+            num: num.parse().ok()?,
+            gen: next()?.parse().ok()?,
+        }),
+    };
+    let x_ppi = next()?.parse().ok()?;
+    let y_ppi = next()?.parse().ok()?;
+    let size = parse_size(next()?)?;
+    let ratio = next()?.strip_suffix('%')?.parse().ok()?;
+
+    Some(ImageInfo {
+        page,
+        num,
+        image_type,
+        width,
+        height,
+        color,
+        comp,
+        bpc,
+        encoding,
+        interpolate,
+        object_id,
+        x_ppi,
+        y_ppi,
+        size,
+        ratio,
+    })
+}
+
+// sizes are printed like 943B, 14.7K or 1.02M
+fn parse_size(size: &str) -> Option<u64> {
+    let unit = match size.chars().last()? {
+        'B' => 1,
+        'K' => 1 << 10,
+        'M' => 1 << 20,
+        'G' => 1 << 30,
+        _ => return None,
+    };
+    let value: f64 = size[..size.len() - 1].parse().ok()?;
+
+    Some((value * unit as f64).round() as u64)
+}
@@ -0,0 +1,367 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+// A point in time from a PDF date in any of the forms pdfinfo prints, raw
+// "D:YYYYMMDDHHmmSSOHH'mm'", ISO 8601 (-isodates and XMP) or the C locale
+// "%c %Z". Dates compare by their UTC time. offset_minutes is the offset
+// the date was written with, None when it had none and was taken as UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PdfDate {
+    pub timestamp: i64,              // seconds since 1970-01-01T00:00:00Z
+    pub offset_minutes: Option<i32>, // east of UTC
+}
+
+impl PdfDate {
+    // Missing parts of partial dates ("D:2023", "2023-04") are the start of
+    // the period. None for anything that is not a date.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if let Some(raw) = value.strip_prefix("D:") {
+            return parse_raw(raw);
+        }
+        // some producers leave out the "D:", at least the full day is needed
+        // to tell those from ISO dates with an error
+        let without_prefix = || {
+            let digits = value.bytes().take_while(u8::is_ascii_digit).count();
+            (digits >= 8).then(|| parse_raw(value)).flatten()
+        };
+        parse_iso(value)
+            .or_else(without_prefix)
+            .or_else(|| parse_locale(value))
+    }
+
+    // the date and time as written, before the offset is applied
+    fn local(self) -> CivilTime {
+        let seconds = self.timestamp + i64::from(self.offset_minutes.unwrap_or(0)) * 60;
+        CivilTime::from_seconds(seconds)
+    }
+
+    // "2023-04-01T10:20:30+02:00", "Z" for UTC and dates without an offset
+    pub fn to_iso_string(self) -> String {
+        let local = self.local();
+        let offset = match self.offset_minutes {
+            None | Some(0) => "Z".to_string(),
+            Some(offset) => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                format!("{}{:02}:{:02}", sign, offset.abs() / 60, offset.abs() % 60)
+            }
+        };
+        format!("{}{}", local.format_iso(), offset)
+    }
+
+    // "D:20230401102030+02'00'", "D:20230401102030" without an offset
+    pub fn to_raw_string(self) -> String {
+        let local = self.local();
+        let offset = match self.offset_minutes {
+            None => String::new(),
+            Some(0) => "Z".to_string(),
+            Some(offset) => {
+                let sign = if offset < 0 { '-' } else { '+' };
+                format!("{}{:02}'{:02}'", sign, offset.abs() / 60, offset.abs() % 60)
+            }
+        };
+        format!(
+            "D:{:04}{:02}{:02}{:02}{:02}{:02}{}",
+            local.year, local.month, local.day, local.hour, local.minute, local.second, offset
+        )
+    }
+
+    // as pdfinfo prints it by default in the C locale with TZ=UTC,
+    // "Sat Apr  1 08:20:30 2023 UTC"
+    pub fn to_locale_string(self) -> String {
+        let utc = CivilTime::from_seconds(self.timestamp);
+        format!(
+            "{} {} {:>2} {:02}:{:02}:{:02} {} UTC",
+            WEEKDAYS[utc.weekday],
+            MONTHS[utc.month as usize - 1],
+            utc.day,
+            utc.hour,
+            utc.minute,
+            utc.second,
+            utc.year
+        )
+    }
+}
+
+impl Ord for PdfDate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.timestamp
+            .cmp(&other.timestamp)
+            .then(self.offset_minutes.cmp(&other.offset_minutes))
+    }
+}
+
+impl PartialOrd for PdfDate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for PdfDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_iso_string())
+    }
+}
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+// "YYYYMMDDHHmmSSOHH'mm'" after the "D:", everything after the year optional
+// as poppler's parseDateString allows, the apostrophes are often left out
+fn parse_raw(value: &str) -> Option<PdfDate> {
+    let mut rest = value;
+    let mut take_number = |digits: usize| -> Option<u32> {
+        let number = rest.get(..digits)?;
+        if !number.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        rest = &rest[digits..];
+        number.parse().ok()
+    };
+
+    let time = CivilTime {
+        year: i64::from(take_number(4)?),
+        month: take_number(2).unwrap_or(1),
+        day: take_number(2).unwrap_or(1),
+        hour: take_number(2).unwrap_or(0),
+        minute: take_number(2).unwrap_or(0),
+        second: take_number(2).unwrap_or(0),
+        weekday: 0,
+    };
+    let offset_minutes = match rest.chars().next() {
+        None => None,
+        Some('Z') => Some(0),
+        Some(sign @ ('+' | '-')) => {
+            let offset = rest[1..].replace('\'', "");
+            let hours: i32 = offset.get(..2)?.parse().ok()?;
+            let minutes: i32 = offset.get(2..4).and_then(|m| m.parse().ok()).unwrap_or(0);
+            let offset = hours * 60 + minutes;
+            Some(if sign == '-' { -offset } else { offset })
+        }
+        Some(_) => return None,
+    };
+
+    time.to_date(offset_minutes)
+}
+
+// "YYYY[-MM[-DD[THH:MM[:SS[.s]]]]]" with "Z", "+HH", "+HH:MM" or "+HHMM"
+fn parse_iso(value: &str) -> Option<PdfDate> {
+    let (date, time) = match value.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    let mut date_parts = date.split('-');
+    let year = date_parts.next().filter(|year| year.len() == 4)?;
+    let mut civil = CivilTime {
+        year: year.parse().ok()?,
+        month: parse_two_digits(date_parts.next().unwrap_or("01"))?,
+        day: parse_two_digits(date_parts.next().unwrap_or("01"))?,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        weekday: 0,
+    };
+    if date_parts.next().is_some() {
+        return None;
+    }
+
+    let Some(time) = time else {
+        return civil.to_date(None);
+    };
+    let offset_start = time.find(['Z', '+', '-']).unwrap_or(time.len());
+    let (clock, offset) = time.split_at(offset_start);
+
+    let mut clock_parts = clock.split(':');
+    civil.hour = parse_two_digits(clock_parts.next()?)?;
+    civil.minute = parse_two_digits(clock_parts.next()?)?;
+    if let Some(second) = clock_parts.next() {
+        // fractions of a second are dropped
+        civil.second = parse_two_digits(second.split('.').next()?)?;
+    }
+    if clock_parts.next().is_some() {
+        return None;
+    }
+
+    let offset_minutes = match offset {
+        "" => None,
+        "Z" => Some(0),
+        offset => {
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let digits = offset[1..].replace(':', "");
+            let hours: i32 = digits.get(..2)?.parse().ok()?;
+            let minutes: i32 = match digits.get(2..) {
+                Some("") | None => 0,
+                Some(minutes) => minutes.parse().ok()?,
+            };
+            Some(sign * (hours * 60 + minutes))
+        }
+    };
+
+    civil.to_date(offset_minutes)
+}
+
+// "%c %Z" of the C locale, "Sat Apr  1 08:20:30 2023 UTC", or of en_US,
+// "Sat 01 Apr 2023 08:20:30 AM UTC". Only UTC, GMT and numeric zones are
+// known, other zone names are ambiguous.
+fn parse_locale(value: &str) -> Option<PdfDate> {
+    let mut civil = CivilTime {
+        year: 0,
+        month: 0,
+        day: 0,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        weekday: 0,
+    };
+    let mut meridiem = None;
+    let mut offset_minutes = None;
+    let mut has_time = false;
+
+    for token in value.split_whitespace() {
+        if let Some(month) = MONTHS.iter().position(|month| *month == token) {
+            civil.month = month as u32 + 1;
+        } else if WEEKDAYS.contains(&token) {
+            // implied by the date
+        } else if token.contains(':') {
+            let mut parts = token.split(':');
+            civil.hour = parts.next()?.parse().ok()?;
+            civil.minute = parts.next()?.parse().ok()?;
+            civil.second = parts.next().map_or(Some(0), |second| second.parse().ok())?;
+            has_time = true;
+        } else if token == "AM" || token == "PM" {
+            meridiem = Some(token);
+        } else if token == "UTC" || token == "GMT" {
+            offset_minutes = Some(0);
+        } else if let Some(offset) = token.strip_prefix(['+', '-']) {
+            let hours: i32 = offset.get(..2)?.parse().ok()?;
+            let minutes: i32 = offset
+                .get(2..)
+                .filter(|m| !m.is_empty())
+                .map_or(Some(0), |m| m.parse().ok())?;
+            let sign = if token.starts_with('-') { -1 } else { 1 };
+            offset_minutes = Some(sign * (hours * 60 + minutes));
+        } else if token.len() == 4 && token.bytes().all(|byte| byte.is_ascii_digit()) {
+            civil.year = token.parse().ok()?;
+        } else if token.len() <= 2 && token.bytes().all(|byte| byte.is_ascii_digit()) {
+            civil.day = token.parse().ok()?;
+        } else {
+            return None;
+        }
+    }
+
+    match meridiem {
+        Some("AM") if civil.hour == 12 => civil.hour = 0,
+        Some("PM") if civil.hour < 12 => civil.hour += 12,
+        _ => {}
+    }
+    if civil.year == 0 || civil.month == 0 || civil.day == 0 || !has_time {
+        return None;
+    }
+
+    civil.to_date(Some(offset_minutes?))
+}
+
+fn parse_two_digits(value: &str) -> Option<u32> {
+    if value.len() != 2 || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
+
+// a date and time of the proleptic Gregorian calendar
+struct CivilTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    weekday: usize, // 0 is Sunday, only set by from_seconds
+}
+
+impl CivilTime {
+    fn to_date(&self, offset_minutes: Option<i32>) -> Option<PdfDate> {
+        let valid = (1..=12).contains(&self.month)
+            && (1..=days_in_month(self.year, self.month)).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 61;
+        if !valid {
+            return None;
+        }
+
+        let seconds = days_from_civil(self.year, self.month, self.day) * 86_400
+            + i64::from(self.hour * 3600 + self.minute * 60 + self.second);
+        Some(PdfDate {
+            timestamp: seconds - i64::from(offset_minutes.unwrap_or(0)) * 60,
+            offset_minutes,
+        })
+    }
+
+    fn from_seconds(seconds: i64) -> Self {
+        let days = seconds.div_euclid(86_400);
+        let time = seconds.rem_euclid(86_400) as u32;
+        let (year, month, day) = civil_from_days(days);
+
+        CivilTime {
+            year,
+            month,
+            day,
+            hour: time / 3600,
+            minute: time % 3600 / 60,
+            second: time % 60,
+            // 1970-01-01 was a Thursday
+            weekday: (days + 4).rem_euclid(7) as usize,
+        }
+    }
+
+    fn format_iso(&self) -> String {
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+// days since 1970-01-01 of a proleptic Gregorian date, after Howard Hinnant
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    (year, month, day)
+}
@@ -0,0 +1,138 @@
+// one row of -dests. Coordinates that the destination leaves unchanged
+// (null) and the ones its kind does not have are None.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedDestination {
+    pub name: String,
+    pub page: u32,
+    pub kind: DestinationKind,
+    pub left: Option<f64>,
+    pub top: Option<f64>,
+    pub right: Option<f64>,
+    pub bottom: Option<f64>,
+    pub zoom: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DestinationKind {
+    Xyz, // left top zoom
+    Fit,
+    FitH, // top
+    FitV, // left
+    FitR, // left bottom right top
+    FitB,
+    FitBH, // top
+    FitBV, // left
+}
+
+impl DestinationKind {
+    const NAMES: [(&'static str, DestinationKind); 8] = [
+        ("XYZ", DestinationKind::Xyz),
+        ("Fit", DestinationKind::Fit),
+        ("FitH", DestinationKind::FitH),
+        ("FitV", DestinationKind::FitV),
+        ("FitR", DestinationKind::FitR),
+        ("FitB", DestinationKind::FitB),
+        ("FitBH", DestinationKind::FitBH),
+        ("FitBV", DestinationKind::FitBV),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::NAMES
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|(_, kind)| *kind)
+    }
+}
+
+// The names among `names` (the ones links are made to) that do not lead to
+// a page of the document. pdfinfo can not list broken destinations: it leaves
+// out every destination whose page does not exist or is not a page at all,
+// and names that are not in the document. So a name is broken when it is
+// missing from destinations, which must be the ones of every page
+// (pdf_info_destinations with first_page 1 and last_page 0).
+pub fn missing_destinations<'a>(
+    destinations: &[NamedDestination],
+    names: &[&'a str],
+) -> Vec<&'a str> {
+    names
+        .iter()
+        .filter(|name| {
+            !destinations
+                .iter()
+                .any(|destination| destination.name == **name)
+        })
+        .copied()
+        .collect()
+}
+
+// "Page  Destination                 Name" and rows like
+// "   1 [ XYZ    0  792 null      ] \"chapter-1\"". Coordinates are rounded to
+// whole points. The destination column is cut to 26 characters, the ']'
+// taking the place of the 27th, so a value running up to the ']' may have
+// lost its last digits and is None. That is always the top of a FitR, whose
+// four values fill the column.
+pub(crate) fn parse_destinations(stdout: &str) -> Result<Vec<NamedDestination>, std::io::Error> {
+    stdout
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_destination(line).ok_or_else(|| unexpected_output(line)))
+        .collect()
+}
+
+fn parse_destination(line: &str) -> Option<NamedDestination> {
+    let (page, rest) = line.trim_start().split_once(' ')?;
+    let (destination, name) = rest.trim_start().strip_prefix('[')?.split_once(']')?;
+    let name = name.strip_prefix(" \"")?.strip_suffix('"')?;
+
+    let mut fields: Vec<&str> = destination.split_whitespace().collect();
+    if !destination.ends_with(' ') {
+        // cut off, possibly down to a lone "-"
+        fields.pop();
+    }
+    let kind = DestinationKind::from_name(fields.first()?)?;
+    let mut values = Vec::new();
+    for field in &fields[1..] {
+        values.push(match *field {
+            "null" => None,
+            field => Some(field.parse::<f64>().ok()?),
+        });
+    }
+    let value = |index: usize| values.get(index).copied().flatten();
+
+    let mut destination = NamedDestination {
+        name: name.to_string(),
+        page: page.parse().ok()?,
+        kind,
+        left: None,
+        top: None,
+        right: None,
+        bottom: None,
+        zoom: None,
+    };
+    match kind {
+        DestinationKind::Xyz => {
+            destination.left = value(0);
+            destination.top = value(1);
+            destination.zoom = value(2);
+        }
+        DestinationKind::FitH | DestinationKind::FitBH => destination.top = value(0),
+        DestinationKind::FitV | DestinationKind::FitBV => destination.left = value(0),
+        DestinationKind::FitR => {
+            destination.left = value(0);
+            destination.bottom = value(1);
+            destination.right = value(2);
+            destination.top = value(3);
+        }
+        DestinationKind::Fit | DestinationKind::FitB => {}
+    }
+
+    Some(destination)
+}
+
+fn unexpected_output(line: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected pdfinfo output: {}", line),
+    )
+}
@@ -0,0 +1,123 @@
+// How an encrypted document is protected, from the "Encrypted:" line.
+// pdfinfo only prints the algorithm and four of the permissions. The key
+// length is only known where the algorithm fixes it, and the permissions
+// pdfinfo does not print are None until a source for them is added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EncryptionInfo {
+    pub algorithm: EncryptionAlgorithm,
+    pub key_length: Option<u32>, // in bits, None for RC4
+    pub permissions: Permissions,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncryptionAlgorithm {
+    Rc4,    // 40 to 128 bit keys
+    Aes,    // 128 bit keys
+    Aes256, // 256 bit keys
+    Unknown,
+}
+
+// The permissions the document grants without the owner password, as its
+// P entry sets them (bits 3 to 12).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Permissions {
+    pub print: bool,
+    pub print_high_res: Option<bool>, // not printed by pdfinfo
+    pub copy: bool,
+    pub change: bool,
+    pub add_notes: bool,
+    pub fill_forms: Option<bool>, // not printed by pdfinfo
+    pub extract_for_accessibility: Option<bool>, // not printed by pdfinfo
+    pub assemble: Option<bool>,   // not printed by pdfinfo
+}
+
+impl EncryptionInfo {
+    // Whether text may be copied out, the copy permission alone, the way
+    // pdftotext decides. extract_for_accessibility is not looked at, pdfinfo
+    // does not print it, so a document that only allows extraction for
+    // accessibility counts as not extractable.
+    pub fn can_extract_text(&self) -> bool {
+        self.permissions.copy
+    }
+}
+
+// "yes (print:yes copy:no change:no addNotes:no algorithm:AES-256)", None
+// for "no". The bundled pdfinfo always prints the details.
+pub(crate) fn parse_encryption(
+    line: &str,
+    value: &str,
+) -> Result<Option<EncryptionInfo>, std::io::Error> {
+    if value == "no" {
+        return Ok(None);
+    }
+    let Some(details) = value
+        .strip_prefix("yes (")
+        .and_then(|details| details.strip_suffix(')'))
+    else {
+        return Err(unexpected_output(line));
+    };
+
+    let mut print = None;
+    let mut copy = None;
+    let mut change = None;
+    let mut add_notes = None;
+    let mut algorithm = EncryptionAlgorithm::Unknown;
+    for field in details.split(' ') {
+        let Some((name, value)) = field.split_once(':') else {
+            return Err(unexpected_output(line));
+        };
+        let flag = match value {
+            "yes" => Some(true),
+            "no" => Some(false),
+            _ => None,
+        };
+        match name {
+            "print" => print = flag,
+            "copy" => copy = flag,
+            "change" => change = flag,
+            "addNotes" => add_notes = flag,
+            "algorithm" => {
+                algorithm = match value {
+                    "RC4" => EncryptionAlgorithm::Rc4,
+                    "AES" => EncryptionAlgorithm::Aes,
+                    "AES-256" => EncryptionAlgorithm::Aes256,
+                    _ => EncryptionAlgorithm::Unknown,
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (Some(print), Some(copy), Some(change), Some(add_notes)) = (print, copy, change, add_notes)
+    else {
+        return Err(unexpected_output(line));
+    };
+
+    let key_length = match algorithm {
+        EncryptionAlgorithm::Aes => Some(128),
+        EncryptionAlgorithm::Aes256 => Some(256),
+        EncryptionAlgorithm::Rc4 | EncryptionAlgorithm::Unknown => None,
+    };
+
+    Ok(Some(EncryptionInfo {
+        algorithm,
+        key_length,
+        permissions: Permissions {
+            print,
+            print_high_res: None,
+            copy,
+            change,
+            add_notes,
+            fill_forms: None,
+            extract_for_accessibility: None,
+            assemble: None,
+        },
+    }))
+}
+
+fn unexpected_output(line: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected pdfinfo output: {}", line),
+    )
+}
@@ -0,0 +1,137 @@
+// where a script of -js is attached
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptLocation {
+    NameTree(String), // the document's JavaScript name tree, under this name
+    Document,         // Before Close Document, Before Save Document, ...
+    Page,             // Page Open, Page Close
+    Annotation,       // Link, Screen and Widget Annotation actions
+    Field,            // Field Activated, Format Field, Validate Field, ...
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfScript {
+    pub location: ScriptLocation,
+    pub trigger: Option<String>, // as pdfinfo names it, None in the name tree
+    pub rendition: bool,         // run by a Rendition action
+    pub page: Option<u32>,       // for Page and Annotation scripts of pdf_javascript_by_page
+    pub source: String,
+}
+
+const DOCUMENT_TRIGGERS: [&str; 5] = [
+    "Before Close Document",
+    "Before Save Document",
+    "After Save Document",
+    "Before Print Document",
+    "After Print Document",
+];
+const PAGE_TRIGGERS: [&str; 2] = ["Page Open", "Page Close"];
+const FIELD_TRIGGERS: [&str; 5] = [
+    "Field Activated",
+    "Field Modified",
+    "Format Field",
+    "Validate Field",
+    "Calculate Field",
+];
+const ANNOTATION_PREFIXES: [&str; 3] = [
+    "Link Annotation ",
+    "Screen Annotation ",
+    "Widget Annotation ",
+];
+
+// Each script is a "label:" line, the source and a blank line. The source
+// may contain anything, including lines that look like labels, so a label
+// only starts a script right after that blank line, and only when it does not
+// go back to an earlier part of the output: pdfinfo writes the name tree,
+// then the document, field, and page and annotation scripts, in that order.
+// A source with a blank line followed by a later label still reads as two
+// scripts, the output can not tell them apart.
+pub(crate) fn parse_javascript(stdout: &str) -> Result<Vec<PdfScript>, std::io::Error> {
+    let mut scripts: Vec<PdfScript> = Vec::new();
+    let mut after_blank_line = true;
+    for line in stdout.lines() {
+        let label = parse_label(line).filter(|label| {
+            after_blank_line
+                && scripts
+                    .last()
+                    .is_none_or(|script| part(&script.location) <= part(&label.location))
+        });
+        match (label, scripts.last_mut()) {
+            (Some(script), _) => scripts.push(script),
+            (None, Some(script)) => {
+                script.source.push_str(line);
+                script.source.push('\n');
+            }
+            (None, None) if line.trim().is_empty() => {}
+            (None, None) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Unexpected pdfinfo output: {}", line),
+                ))
+            }
+        }
+        after_blank_line = line.is_empty();
+    }
+
+    // the line ends and the blank line after every script
+    for script in &mut scripts {
+        let source = script.source.strip_suffix('\n').unwrap_or(&script.source);
+        let source = source.strip_suffix('\n').unwrap_or(source);
+        script.source = source.to_string();
+    }
+
+    Ok(scripts)
+}
+
+// the order pdfinfo writes the locations in
+fn part(location: &ScriptLocation) -> u8 {
+    match location {
+        ScriptLocation::NameTree(_) => 0,
+        ScriptLocation::Document => 1,
+        ScriptLocation::Field => 2,
+        ScriptLocation::Page | ScriptLocation::Annotation => 3,
+    }
+}
+
+fn parse_label(line: &str) -> Option<PdfScript> {
+    let label = line.strip_suffix(':')?;
+
+    if let Some(name) = label
+        .strip_prefix("Name Dictionary \"")
+        .and_then(|name| name.strip_suffix('"'))
+    {
+        return Some(PdfScript {
+            location: ScriptLocation::NameTree(name.to_string()),
+            trigger: None,
+            rendition: false,
+            page: None,
+            source: String::new(),
+        });
+    }
+
+    let (trigger, rendition) = match label.strip_suffix(" (Rendition)") {
+        Some(trigger) => (trigger, true),
+        None => (label, false),
+    };
+    let location = if DOCUMENT_TRIGGERS.contains(&trigger) {
+        ScriptLocation::Document
+    } else if PAGE_TRIGGERS.contains(&trigger) {
+        ScriptLocation::Page
+    } else if FIELD_TRIGGERS.contains(&trigger) {
+        ScriptLocation::Field
+    } else if ANNOTATION_PREFIXES
+        .iter()
+        .any(|prefix| trigger.starts_with(prefix))
+    {
+        ScriptLocation::Annotation
+    } else {
+        return None;
+    };
+
+    Some(PdfScript {
+        location,
+        trigger: Some(trigger.to_string()),
+        rendition,
+        page: None,
+        source: String::new(),
+    })
+}
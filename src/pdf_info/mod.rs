@@ -0,0 +1,795 @@
+use crate::utils::{run_program, run_program_raw, PopplerFile};
+use std::collections::BTreeMap;
+use std::future::Future;
+
+mod date;
+mod destinations;
+mod encryption;
+mod javascript;
+mod structure;
+mod urls;
+mod xmp;
+
+pub use crate::utils::ObjectId;
+pub use crate::utils::Rect;
+pub use date::PdfDate;
+pub use destinations::{missing_destinations, DestinationKind, NamedDestination};
+pub use encryption::{EncryptionAlgorithm, EncryptionInfo, Permissions};
+pub use javascript::{PdfScript, ScriptLocation};
+pub use structure::{StructAttribute, StructDisplay, StructNode, StructTree, StructTreeIter};
+pub use urls::{unique_urls, LinkAnnotation, LinkUrl, UrlKind};
+pub use xmp::{PdfAIdentification, XmpMetadata};
+
+pub(crate) use destinations::parse_destinations;
+pub(crate) use javascript::parse_javascript;
+pub(crate) use structure::parse_struct_tree;
+pub(crate) use urls::parse_urls;
+pub(crate) use xmp::parse_xmp_metadata;
+
+pub struct PdfInfoConfig<'a> {
+    pub first_page: i32,            // firstPage, -f
+    pub last_page: i32,             // lastPage, -l
+    pub print_boxes: bool,          // printBoxes, -box
+    pub print_metadata: bool,       // printMetadata, -meta
+    pub print_custom: bool,         // printCustom, -custom
+    pub print_js: bool,             // printJS, -js
+    pub print_structure: bool,      // printStructure, -struct
+    pub print_structure_text: bool, // printStructureText, -struct-text
+    pub iso_dates: bool,            // isoDates, -isodates
+    pub raw_dates: bool,            // rawDates, -rawdates
+    pub print_dests: bool,          // printDests, -dests
+    pub print_urls: bool,           // printUrls, -url
+    pub output_encoding: &'a str,   // textEncName, -enc
+    pub print_enc: bool,            // printEnc, -listenc
+    pub owner_password: &'a str,    // ownerPassword, -opw
+    pub user_password: &'a str,     // userPassword, -upw
+    pub print_version: bool,        // printVersion, -v
+    pub print_help: bool,           // printHelp, -h
+}
+
+impl<'a> Default for PdfInfoConfig<'a> {
+    fn default() -> Self {
+        Self {
+            first_page: 1,
+            last_page: 0,
+            print_boxes: false,
+            print_metadata: false,
+            print_custom: false,
+            print_js: false,
+            print_structure: false,
+            print_structure_text: false,
+            iso_dates: false,
+            raw_dates: false,
+            print_dests: false,
+            print_urls: false,
+            output_encoding: "",
+            print_enc: false,
+            owner_password: "",
+            user_password: "",
+            print_version: false,
+            print_help: false,
+        }
+    }
+}
+
+// the document information pdfinfo prints without any of the -meta, -custom,
+// -js, -struct, -dests or -url modes
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PdfInfo {
+    pub title: Option<String>,
+    pub subject: Option<String>,
+    pub keywords: Option<String>,
+    pub author: Option<String>,
+    pub creator: Option<String>,
+    pub producer: Option<String>,
+    pub creation_date: Option<PdfDate>,
+    pub mod_date: Option<PdfDate>,
+    pub custom_metadata: bool,
+    pub metadata_stream: bool,
+    pub tagged: bool,
+    pub user_properties: bool,
+    pub suspects: bool,
+    pub form: FormType,
+    pub javascript: bool,
+    pub pages: u32,
+    pub encryption: Option<EncryptionInfo>, // None when not encrypted
+    pub page_size: Option<PageSize>,        // of first_page
+    pub page_rotation: i32,
+    pub file_size: u64, // 0 for buffers, pdfinfo can not stat stdin
+    pub optimized: bool,
+    pub pdf_version: String,
+}
+
+impl PdfInfo {
+    // EncryptionInfo::can_extract_text, true when not encrypted
+    pub fn can_extract_text(&self) -> bool {
+        self.encryption
+            .is_none_or(|encryption| encryption.can_extract_text())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FormType {
+    #[default]
+    None,
+    AcroForm,
+    Xfa,
+}
+
+// a value of pdf_info_custom, Date for a valid "D:..." date, Text for
+// anything else
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InfoValue {
+    Date(PdfDate),
+    Text(String),
+}
+
+// in points, paper is the name pdfinfo matched ("letter", "A4", ...)
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageSize {
+    pub width: f64,
+    pub height: f64,
+    pub paper: Option<String>,
+}
+
+pub fn pdf_info(
+    file: PopplerFile,
+    options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<String, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+
+    // return the non-awaited future
+    run_program(file, "pdfinfo", parsed_options)
+}
+
+// Same as pdf_info, parsed into a PdfInfo. The -meta, -custom, -js, -struct,
+// -struct-text, -dests and -url modes replace the information pdfinfo prints,
+// so they are ignored here. Without iso_dates the dates are read with
+// -rawdates, the default form is in the local time zone and locale.
+pub fn pdf_info_parsed(
+    file: PopplerFile,
+    options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<PdfInfo, std::io::Error>> {
+    let parsed_options = parsed_info_options(options);
+
+    async move {
+        let stdout = run_program(file, "pdfinfo", parsed_options).await?;
+        parse_pdf_info(&stdout)
+    }
+}
+
+pub(crate) fn parsed_info_options(mut options: PdfInfoConfig) -> Vec<String> {
+    clear_output_modes(&mut options);
+    if !options.iso_dates {
+        options.raw_dates = true;
+    }
+    // Without -l pdfinfo only prints the size of page 1, and nothing at all
+    // when first_page is past it. Asked for as a range of one it prints
+    // "Page    N size:" for first_page.
+    if options.last_page == 0 && options.first_page > 1 {
+        options.last_page = options.first_page;
+    }
+    parse_options(&options)
+}
+
+// The XMP metadata stream of the document, None when it has none. The other
+// output modes are ignored, only -meta is passed.
+pub fn pdf_info_metadata(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<Option<XmpMetadata>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.print_metadata = true;
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout = run_program(file, "pdfinfo", parsed_options).await?;
+        match stdout.is_empty() {
+            true => Ok(None),
+            false => parse_xmp_metadata(&stdout).map(Some),
+        }
+    }
+}
+
+// The Info dictionary entries other than the standard ones (Title, Author,
+// CreationDate, ...), sorted by key. pdfinfo only reads the standard dates,
+// values in the "D:..." date form are read as dates here. iso_dates and
+// raw_dates make no difference.
+pub fn pdf_info_custom(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<BTreeMap<String, InfoValue>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.print_custom = true;
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout = run_program(file, "pdfinfo", parsed_options).await?;
+        Ok(parse_custom_info(&stdout))
+    }
+}
+
+// Every script of the document: the JavaScript name tree, document, form
+// field and page actions, and the annotation actions of the pages from
+// first_page to last_page. The other output modes are ignored. pdfinfo does
+// not say which page a script is on, page is always None.
+pub fn pdf_javascript(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<Vec<PdfScript>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.print_js = true;
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout = run_program_raw(file, "pdfinfo", parsed_options, Vec::new()).await?;
+        parse_javascript(&String::from_utf8_lossy(&stdout))
+    }
+}
+
+// Same as pdf_javascript, with the page of the Page and Annotation scripts.
+// pdfinfo runs once for the page count and once for every page of the range,
+// buffers are sent to it each time, so this is slow for long documents.
+pub fn pdf_javascript_by_page(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<Vec<PdfScript>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    let first_page = options.first_page.max(1) as u32;
+    let last_page = options.last_page;
+    let info_options = parsed_info_options(PdfInfoConfig {
+        first_page: 1,
+        last_page: 0,
+        ..options
+    });
+    options.print_js = true;
+
+    async move {
+        // pdfinfo stops at the last page of the document as well
+        let stdout = run_program(file.clone(), "pdfinfo", info_options).await?;
+        let pages = parse_pdf_info(&stdout)?.pages;
+        let last_page = match last_page {
+            1.. => pages.min(last_page as u32),
+            _ => pages,
+        };
+        if first_page > last_page {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Wrong page range given: {} to {}", first_page, last_page),
+            ));
+        }
+
+        let mut scripts = Vec::new();
+        let mut page_scripts = Vec::new();
+        for page in first_page..=last_page {
+            options.first_page = page as i32;
+            options.last_page = page as i32;
+            let parsed_options = parse_options(&options);
+            let stdout =
+                run_program_raw(file.clone(), "pdfinfo", parsed_options, Vec::new()).await?;
+
+            // the name tree, document and field scripts are the same every run
+            for mut script in parse_javascript(&String::from_utf8_lossy(&stdout))? {
+                match script.location {
+                    ScriptLocation::Page | ScriptLocation::Annotation => {
+                        script.page = Some(page);
+                        page_scripts.push(script);
+                    }
+                    _ if page == first_page => scripts.push(script),
+                    _ => {}
+                }
+            }
+        }
+        scripts.extend(page_scripts);
+
+        Ok(scripts)
+    }
+}
+
+// The logical structure of a tagged PDF, empty for untagged ones. With
+// print_structure_text the nodes carry their text. The other output modes
+// are ignored.
+pub fn pdf_info_structure(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<StructTree, std::io::Error>> {
+    let print_structure_text = options.print_structure_text;
+    clear_output_modes(&mut options);
+    options.print_structure = true;
+    options.print_structure_text = print_structure_text;
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout = run_program(file, "pdfinfo", parsed_options).await?;
+        parse_struct_tree(&stdout)
+    }
+}
+
+// The named destinations pointing at the pages from first_page to
+// last_page, by page and then name. The other output modes are ignored.
+// pdfinfo leaves out every destination that does not point at a page of the
+// document, missing_destinations finds those among the names links use.
+pub fn pdf_info_destinations(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<Vec<NamedDestination>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.print_dests = true;
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout = run_program(file, "pdfinfo", parsed_options).await?;
+        parse_destinations(&stdout)
+    }
+}
+
+// The URI link annotations of the pages from first_page to last_page,
+// each URL once per page. The other output modes are ignored.
+pub fn pdf_urls(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<Vec<LinkAnnotation>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.print_urls = true;
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout = run_program(file, "pdfinfo", parsed_options).await?;
+        parse_urls(&stdout)
+    }
+}
+
+// one page of page_geometry, in points. size is the crop box with the paper
+// name pdfinfo matched, rotation is the page's /Rotate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageGeometry {
+    pub number: u32,
+    pub size: PageSize,
+    pub rotation: i32,
+    pub media_box: Rect,
+    pub crop_box: Rect,
+    pub bleed_box: Rect,
+    pub trim_box: Rect,
+    pub art_box: Rect,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+impl PageGeometry {
+    // as displayed, a quarter turn swaps portrait and landscape
+    pub fn orientation(&self) -> Orientation {
+        let is_wide = self.size.width > self.size.height;
+        let is_turned = self.rotation.rem_euclid(180) == 90;
+        match is_wide != is_turned {
+            true => Orientation::Landscape,
+            false => Orientation::Portrait,
+        }
+    }
+}
+
+// Per-page size, rotation and boxes for first_page to last_page, or to the end
+// of the document when last_page is 0. print_boxes is always set and the
+// output modes are ignored, as for pdf_info_parsed.
+pub fn page_geometry(
+    file: PopplerFile,
+    mut options: PdfInfoConfig<'static>,
+) -> impl Future<Output = Result<Vec<PageGeometry>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.print_boxes = true;
+    // pdfinfo only numbers its page lines when -l is given, and clamps it
+    // to the page count
+    if options.last_page == 0 {
+        options.last_page = i32::MAX;
+    }
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout = run_program(file, "pdfinfo", parsed_options).await?;
+        parse_page_geometry(&stdout)
+    }
+}
+
+// true when the pages differ in size, whichever way round they are
+pub fn has_mixed_page_sizes(pages: &[PageGeometry]) -> bool {
+    // pdfinfo itself allows 1pt when it names paper sizes
+    const TOLERANCE: f64 = 1.0;
+
+    let dimensions = |page: &PageGeometry| {
+        let (width, height) = (page.size.width, page.size.height);
+        (width.min(height), width.max(height))
+    };
+    pages.windows(2).any(|pair| {
+        let (a, b) = (dimensions(&pair[0]), dimensions(&pair[1]));
+        (a.0 - b.0).abs() >= TOLERANCE || (a.1 - b.1).abs() >= TOLERANCE
+    })
+}
+
+pub fn has_mixed_orientations(pages: &[PageGeometry]) -> bool {
+    pages
+        .windows(2)
+        .any(|pair| pair[0].orientation() != pair[1].orientation())
+}
+
+// the numbered "Page    N key:" lines of -l with -box, other lines are skipped
+pub(crate) fn parse_page_geometry(stdout: &str) -> Result<Vec<PageGeometry>, std::io::Error> {
+    let mut pages: BTreeMap<u32, PartialPage> = BTreeMap::new();
+    for line in stdout.lines() {
+        let Some((number, key, value)) = split_page_line(line) else {
+            continue;
+        };
+        let page = pages.entry(number).or_default();
+        let box_index = match key {
+            "size" => {
+                page.size = Some(parse_page_size(value).ok_or_else(|| unexpected_output(line))?);
+                continue;
+            }
+            "rot" => {
+                page.rotation = Some(value.parse().map_err(|_| unexpected_output(line))?);
+                continue;
+            }
+            "MediaBox" => 0,
+            "CropBox" => 1,
+            "BleedBox" => 2,
+            "TrimBox" => 3,
+            "ArtBox" => 4,
+            _ => return Err(unexpected_output(line)),
+        };
+        page.boxes[box_index] = Some(parse_box(value).ok_or_else(|| unexpected_output(line))?);
+    }
+
+    pages
+        .into_iter()
+        .map(|(number, page)| {
+            page.complete(number).ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Unexpected pdfinfo output: incomplete page {}", number),
+                )
+            })
+        })
+        .collect()
+}
+
+// the lines of one page, collected by parse_page_geometry
+#[derive(Default)]
+struct PartialPage {
+    size: Option<PageSize>,
+    rotation: Option<i32>,
+    boxes: [Option<Rect>; 5], // MediaBox, CropBox, BleedBox, TrimBox, ArtBox
+}
+
+impl PartialPage {
+    fn complete(self, number: u32) -> Option<PageGeometry> {
+        let [media_box, crop_box, bleed_box, trim_box, art_box] = self.boxes;
+
+        Some(PageGeometry {
+            number,
+            size: self.size?,
+            rotation: self.rotation?,
+            media_box: media_box?,
+            crop_box: crop_box?,
+            bleed_box: bleed_box?,
+            trim_box: trim_box?,
+            art_box: art_box?,
+        })
+    }
+}
+
+// "Page    3 MediaBox:     0.00     0.00   612.00   792.00"
+fn split_page_line(line: &str) -> Option<(u32, &str, &str)> {
+    let rest = line.strip_prefix("Page ")?.trim_start();
+    let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+    let number = rest[..digits].parse().ok()?;
+    let (key, value) = rest[digits..].split_once(':')?;
+
+    Some((number, key.trim(), value.trim()))
+}
+
+// "x1 y1 x2 y2"
+fn parse_box(value: &str) -> Option<Rect> {
+    let mut coordinates = value
+        .split_whitespace()
+        .map(|coordinate| coordinate.parse());
+    let rect = Rect {
+        x_min: coordinates.next()?.ok()?,
+        y_min: coordinates.next()?.ok()?,
+        x_max: coordinates.next()?.ok()?,
+        y_max: coordinates.next()?.ok()?,
+    };
+
+    coordinates.next().is_none().then_some(rect)
+}
+
+// "Key:<padded to 16> value" for every entry, standard ones included. Values
+// may span several lines, lines without a ':' continue the one before.
+pub(crate) fn parse_custom_info(stdout: &str) -> BTreeMap<String, InfoValue> {
+    const STANDARD_KEYS: [&str; 9] = [
+        "Title",
+        "Subject",
+        "Keywords",
+        "Author",
+        "Creator",
+        "Producer",
+        "CreationDate",
+        "ModDate",
+        "Trapped",
+    ];
+
+    let mut entries: Vec<(String, String)> = Vec::new();
+    for line in stdout.lines() {
+        match (line.split_once(':'), entries.last_mut()) {
+            (Some((key, value)), _) if !key.is_empty() => {
+                // only the padding, spaces the value starts with are kept
+                let padding = 16usize.saturating_sub(key.chars().count());
+                let spaces = value.len() - value.trim_start_matches(' ').len();
+                let value = &value[spaces.min(padding)..];
+                entries.push((key.to_string(), value.to_string()));
+            }
+            (_, Some((_, value))) => {
+                value.push('\n');
+                value.push_str(line);
+            }
+            (_, None) => {}
+        }
+    }
+
+    entries
+        .into_iter()
+        .filter(|(key, _)| !STANDARD_KEYS.contains(&key.as_str()))
+        .map(|(key, value)| {
+            let date = value
+                .starts_with("D:")
+                .then(|| PdfDate::parse(&value))
+                .flatten();
+            let value = match date {
+                Some(date) => InfoValue::Date(date),
+                None => InfoValue::Text(value),
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+// the modes that replace the document information pdfinfo prints
+fn clear_output_modes(options: &mut PdfInfoConfig) {
+    options.print_metadata = false;
+    options.print_custom = false;
+    options.print_js = false;
+    options.print_structure = false;
+    options.print_structure_text = false;
+    options.print_dests = false;
+    options.print_urls = false;
+}
+
+// "Key:  value" lines, the value starting at column 17. Strings may span
+// several lines, so unknown lines continue the string before them.
+pub(crate) fn parse_pdf_info(stdout: &str) -> Result<PdfInfo, std::io::Error> {
+    let mut info = PdfInfo::default();
+    let mut has_pages = false;
+    let mut has_rotation = false;
+    let mut in_subtype = false;
+    let mut last_string: Option<&mut Option<String>> = None;
+
+    for line in stdout.lines() {
+        // the indented details under "PDF subtype:"
+        if in_subtype && line.starts_with(' ') {
+            continue;
+        }
+        in_subtype = false;
+
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) if is_info_key(key) => (key, value.trim()),
+            _ => {
+                match last_string.as_mut() {
+                    Some(Some(string)) => {
+                        string.push('\n');
+                        string.push_str(line);
+                    }
+                    _ => return Err(unexpected_output(line)),
+                }
+                continue;
+            }
+        };
+        last_string = None;
+
+        let string_field = match key {
+            "Title" => Some(&mut info.title),
+            "Subject" => Some(&mut info.subject),
+            "Keywords" => Some(&mut info.keywords),
+            "Author" => Some(&mut info.author),
+            "Creator" => Some(&mut info.creator),
+            "Producer" => Some(&mut info.producer),
+            _ => None,
+        };
+        if let Some(field) = string_field {
+            *field = Some(value.to_string());
+            last_string = Some(field);
+            continue;
+        }
+
+        match key {
+            "CreationDate" => info.creation_date = PdfDate::parse(value),
+            "ModDate" => info.mod_date = PdfDate::parse(value),
+            "Custom Metadata" => info.custom_metadata = parse_yes_no(line, value)?,
+            "Metadata Stream" => info.metadata_stream = parse_yes_no(line, value)?,
+            "Tagged" => info.tagged = parse_yes_no(line, value)?,
+            "UserProperties" => info.user_properties = parse_yes_no(line, value)?,
+            "Suspects" => info.suspects = parse_yes_no(line, value)?,
+            "Form" => {
+                info.form = match value {
+                    "none" => FormType::None,
+                    "AcroForm" => FormType::AcroForm,
+                    "XFA" => FormType::Xfa,
+                    _ => return Err(unexpected_output(line)),
+                }
+            }
+            "JavaScript" => info.javascript = parse_yes_no(line, value)?,
+            "Pages" => {
+                info.pages = value.parse().map_err(|_| unexpected_output(line))?;
+                has_pages = true;
+            }
+            // "yes (print:yes copy:no ...)" for encrypted files
+            "Encrypted" => info.encryption = encryption::parse_encryption(line, value)?,
+            "File size" => {
+                info.file_size = value
+                    .trim_end_matches("bytes")
+                    .trim()
+                    .parse()
+                    .map_err(|_| unexpected_output(line))?
+            }
+            "Optimized" => info.optimized = parse_yes_no(line, value)?,
+            "PDF version" => info.pdf_version = value.to_string(),
+            "PDF subtype" => in_subtype = true,
+            // "Page size:" or "Page    1 size:" once -l is given, only the
+            // first page is kept
+            key if key.ends_with("size") && info.page_size.is_none() => {
+                info.page_size =
+                    Some(parse_page_size(value).ok_or_else(|| unexpected_output(line))?);
+            }
+            key if key.ends_with("rot") && !has_rotation => {
+                info.page_rotation = value.parse().map_err(|_| unexpected_output(line))?;
+                has_rotation = true;
+            }
+            // later pages and the -box lines
+            _ => {}
+        }
+    }
+
+    if !has_pages {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "Unexpected pdfinfo output: no page count",
+        ));
+    }
+
+    Ok(info)
+}
+
+fn is_info_key(key: &str) -> bool {
+    const KEYS: [&str; 21] = [
+        "Title",
+        "Subject",
+        "Keywords",
+        "Author",
+        "Creator",
+        "Producer",
+        "CreationDate",
+        "ModDate",
+        "Custom Metadata",
+        "Metadata Stream",
+        "Tagged",
+        "UserProperties",
+        "Suspects",
+        "Form",
+        "JavaScript",
+        "Pages",
+        "Encrypted",
+        "File size",
+        "Optimized",
+        "PDF version",
+        "PDF subtype",
+    ];
+    const PAGE_KEYS: [&str; 7] = [
+        "size", "rot", "MediaBox", "CropBox", "BleedBox", "TrimBox", "ArtBox",
+    ];
+
+    // "Page size", "Page    3 rot", "Page    3 MediaBox", or a bare "MediaBox"
+    let page_key = key
+        .strip_prefix("Page")
+        .map(|rest| {
+            rest.trim_start()
+                .trim_start_matches(|c: char| c.is_ascii_digit())
+                .trim_start()
+        })
+        .unwrap_or(key);
+
+    KEYS.contains(&key) || PAGE_KEYS.contains(&page_key)
+}
+
+fn parse_yes_no(line: &str, value: &str) -> Result<bool, std::io::Error> {
+    match value {
+        "yes" => Ok(true),
+        "no" => Ok(false),
+        _ => Err(unexpected_output(line)),
+    }
+}
+
+// "612 x 792 pts (letter)"
+fn parse_page_size(value: &str) -> Option<PageSize> {
+    let (size, paper) = match value.split_once(" pts") {
+        Some((size, paper)) => (size, paper.trim()),
+        None => (value, ""),
+    };
+    let (width, height) = size.split_once(" x ")?;
+    let paper = paper
+        .strip_prefix('(')
+        .and_then(|paper| paper.strip_suffix(')'))
+        .map(|paper| paper.to_string());
+
+    Some(PageSize {
+        width: width.trim().parse().ok()?,
+        height: height.trim().parse().ok()?,
+        paper,
+    })
+}
+
+fn unexpected_output(line: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected pdfinfo output: {}", line),
+    )
+}
+
+fn parse_options(options: &PdfInfoConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(options.first_page != 1, "-f", options.first_page);
+    add_option!(options.last_page != 0, "-l", options.last_page);
+    add_option!(options.print_boxes, "-box");
+    add_option!(options.print_metadata, "-meta");
+    add_option!(options.print_custom, "-custom");
+    add_option!(options.print_js, "-js");
+    add_option!(options.print_structure, "-struct");
+    add_option!(options.print_structure_text, "-struct-text");
+    add_option!(options.iso_dates, "-isodates");
+    add_option!(options.raw_dates, "-rawdates");
+    add_option!(options.print_dests, "-dests");
+    add_option!(options.print_urls, "-url");
+    add_option!(
+        !options.output_encoding.is_empty(),
+        "-enc",
+        options.output_encoding
+    );
+    add_option!(options.print_enc, "-listenc");
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+    add_option!(options.print_version, "-v");
+    add_option!(options.print_help, "-h");
+
+    parsed_options
+}
@@ -0,0 +1,258 @@
+use crate::utils::ObjectId;
+
+// The logical structure of a tagged PDF, as -struct prints it.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructTree {
+    pub roots: Vec<StructNode>,
+}
+
+// A structure element. There is no alt text: pdfinfo prints neither the /Alt
+// nor the /ActualText entry of an element, with or without -struct-text, and
+// none of the other poppler tools do either. The text of -struct-text is the
+// marked content on the page, a Figure usually has none.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructNode {
+    pub role: String,          // Document, H1, P, Figure, Table, TD, ...
+    pub id: Option<String>,    // /ID
+    pub title: Option<String>, // /T
+    pub revision: u32,         // /R
+    pub display: Option<StructDisplay>,
+    pub attributes: Vec<StructAttribute>,
+    pub text: Option<String>,   // the marked content, only with -struct-text
+    pub objects: Vec<ObjectId>, // object references among the kids
+    pub children: Vec<StructNode>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StructDisplay {
+    Inline,
+    Block,
+}
+
+// " /Placement /Block", " /UserProperty (name) (value) \"formatted\" [hidden]"
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructAttribute {
+    pub name: String,
+    pub user_property: Option<String>, // the name of a UserProperty
+    pub value: String,                 // in PDF syntax, "/Block", "(text)", "[0 0 1]"
+    pub formatted_value: Option<String>,
+    pub hidden: bool,
+}
+
+impl StructTree {
+    // every node, depth first, each before its children
+    pub fn iter(&self) -> StructTreeIter<'_> {
+        StructTreeIter {
+            stack: vec![self.roots.iter()],
+        }
+    }
+}
+
+impl StructNode {
+    // this node and everything below it, depth first
+    pub fn iter(&self) -> StructTreeIter<'_> {
+        StructTreeIter {
+            stack: vec![std::slice::from_ref(self).iter()],
+        }
+    }
+}
+
+pub struct StructTreeIter<'a> {
+    stack: Vec<std::slice::Iter<'a, StructNode>>,
+}
+
+impl<'a> Iterator for StructTreeIter<'a> {
+    type Item = &'a StructNode;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let siblings = self.stack.last_mut()?;
+            match siblings.next() {
+                Some(node) => {
+                    self.stack.push(node.children.iter());
+                    return Some(node);
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a StructTree {
+    type Item = &'a StructNode;
+    type IntoIter = StructTreeIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Two spaces of indent per level. Attributes follow their element one level
+// deeper, content ("text" or "(No content?)") and "Object n g" lines sit
+// among the children.
+pub(crate) fn parse_struct_tree(stdout: &str) -> Result<StructTree, std::io::Error> {
+    let mut roots = Vec::new();
+    // the open elements, the last one is the deepest
+    let mut open: Vec<StructNode> = Vec::new();
+    let mut lines = stdout.lines();
+
+    while let Some(line) = lines.next() {
+        let content = line.trim_start_matches(' ');
+        let indent = line.len() - content.len();
+        if content.is_empty() {
+            continue;
+        }
+
+        // " /Name value", indented one more than its element
+        if let Some(attribute) = content.strip_prefix('/') {
+            let element = open.last_mut().ok_or_else(|| unexpected_output(line))?;
+            element.attributes.push(parse_attribute(attribute));
+            continue;
+        }
+
+        let depth = indent / 2;
+        if depth > open.len() {
+            return Err(unexpected_output(line));
+        }
+        close_elements(&mut open, &mut roots, depth);
+
+        if content.starts_with('"') || content == "(No content?)" {
+            let mut text = content.to_string();
+            // text with line breaks continues up to the closing quote
+            while text.starts_with('"') && (text.len() < 2 || !text.ends_with('"')) {
+                let Some(next) = lines.next() else { break };
+                text.push('\n');
+                text.push_str(next);
+            }
+            let element = open.last_mut().ok_or_else(|| unexpected_output(line))?;
+            if let Some(text) = text
+                .strip_prefix('"')
+                .and_then(|text| text.strip_suffix('"'))
+            {
+                element.text.get_or_insert_with(String::new).push_str(text);
+            }
+        } else if let Some(object_id) = parse_object_line(content) {
+            let element = open.last_mut().ok_or_else(|| unexpected_output(line))?;
+            element.objects.push(object_id);
+        } else {
+            open.push(parse_element(content).ok_or_else(|| unexpected_output(line))?);
+        }
+    }
+    close_elements(&mut open, &mut roots, 0);
+
+    Ok(StructTree { roots })
+}
+
+// hands the elements deeper than depth to their parents
+fn close_elements(open: &mut Vec<StructNode>, roots: &mut Vec<StructNode>, depth: usize) {
+    while open.len() > depth {
+        let element = open.pop().unwrap();
+        match open.last_mut() {
+            Some(parent) => parent.children.push(element),
+            None => roots.push(element),
+        }
+    }
+}
+
+// "Object 12 0"
+fn parse_object_line(content: &str) -> Option<ObjectId> {
+    let mut parts = content.strip_prefix("Object ")?.split(' ');
+    let object_id = ObjectId {
+        num: parts.next()?.parse().ok()?,
+        gen: parts.next()?.parse().ok()?,
+    };
+    parts.next().is_none().then_some(object_id)
+}
+
+// "Role <id> \"title\" r2 (block):", everything after the role optional
+fn parse_element(content: &str) -> Option<StructNode> {
+    // the ':' only announces attributes
+    let rest = content.strip_suffix(':').unwrap_or(content);
+    let mut node = StructNode::default();
+
+    let (mut rest, display) = if let Some(rest) = rest.strip_suffix(" (inline)") {
+        (rest, Some(StructDisplay::Inline))
+    } else if let Some(rest) = rest.strip_suffix(" (block)") {
+        (rest, Some(StructDisplay::Block))
+    } else {
+        (rest, None)
+    };
+    node.display = display;
+
+    if let Some((before, revision)) = rest.rsplit_once(" r") {
+        if let Ok(revision) = revision.parse() {
+            node.revision = revision;
+            rest = before;
+        }
+    }
+
+    let role_end = rest.find(' ').unwrap_or(rest.len());
+    node.role = rest[..role_end].to_string();
+    rest = &rest[role_end..];
+    if node.role.is_empty() {
+        return None;
+    }
+
+    if let Some(id) = rest.strip_prefix(" <") {
+        let end = id.find('>')?;
+        node.id = Some(id[..end].to_string());
+        rest = &id[end + 1..];
+    }
+    if let Some(title) = rest.strip_prefix(" \"") {
+        node.title = Some(title.strip_suffix('"')?.to_string());
+        rest = "";
+    }
+
+    rest.is_empty().then_some(node)
+}
+
+// "Name value", "UserProperty (name) value", with " \"formatted\"" and
+// " [hidden]" after the value
+fn parse_attribute(attribute: &str) -> StructAttribute {
+    let (rest, hidden) = match attribute.strip_suffix(" [hidden]") {
+        Some(rest) => (rest, true),
+        None => (attribute, false),
+    };
+    let (name, mut value) = rest.split_once(' ').unwrap_or((rest, ""));
+
+    let mut user_property = None;
+    if name == "UserProperty" {
+        if let Some((property, rest)) = value
+            .strip_prefix('(')
+            .and_then(|value| value.split_once(") "))
+        {
+            user_property = Some(property.to_string());
+            value = rest;
+        }
+    }
+
+    // values in PDF syntax never end in a '"', strings are in parentheses
+    let mut formatted_value = None;
+    if value.ends_with('"') {
+        if let Some((before, formatted)) = value.split_once(" \"") {
+            formatted_value = Some(formatted[..formatted.len() - 1].to_string());
+            value = before;
+        }
+    }
+
+    StructAttribute {
+        name: name.to_string(),
+        user_property,
+        value: value.to_string(),
+        formatted_value,
+        hidden,
+    }
+}
+
+fn unexpected_output(line: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected pdfinfo output: {}", line),
+    )
+}
@@ -0,0 +1,206 @@
+// one URI link annotation of -url
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkAnnotation {
+    pub page: u32,
+    pub kind: UrlKind,
+    pub url: LinkUrl,
+}
+
+// by scheme, http and https are both Http
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlKind {
+    Http,
+    Mailto,
+    File, // file: and bare paths like C:\docs\a.exe
+    JavaScript,
+    Other, // other schemes, and relative URLs without one
+}
+
+// A URL split into its parts. scheme and host are lowercase, raw is the URL
+// as written in the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkUrl {
+    pub raw: String,
+    pub scheme: Option<String>,
+    pub user_info: Option<String>,
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub path: String,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+}
+
+// the schemes browsers read '\' as '/' in
+const SPECIAL_SCHEMES: [&str; 6] = ["http", "https", "ws", "wss", "ftp", "file"];
+
+impl LinkUrl {
+    // Read the way browsers read URLs (WHATWG), not strictly by RFC 3986, so
+    // the parts are the ones a click would use.
+    pub fn parse(raw: &str) -> Self {
+        // viewers ignore leading whitespace and control characters, which
+        // hides schemes like " javascript:" from a plain prefix check, and
+        // drop tabs and line breaks anywhere, "java\tscript:" included
+        let url: String = raw
+            .trim_start_matches(|c: char| c.is_whitespace() || c.is_control())
+            .chars()
+            .filter(|c| !matches!(c, '\t' | '\r' | '\n'))
+            .collect();
+
+        let scheme_end = url.find(':').filter(|end| {
+            let scheme = &url[..*end];
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        });
+        let (scheme, rest) = match scheme_end {
+            Some(end) => (Some(url[..end].to_ascii_lowercase()), &url[end + 1..]),
+            None => (None, url.as_str()),
+        };
+
+        let (rest, fragment) = match rest.split_once('#') {
+            Some((rest, fragment)) => (rest, Some(fragment.to_string())),
+            None => (rest, None),
+        };
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query.to_string())),
+            None => (rest, None),
+        };
+
+        let special = scheme
+            .as_deref()
+            .is_some_and(|scheme| SPECIAL_SCHEMES.contains(&scheme));
+        let rest = match special {
+            true => rest.replace('\\', "/"),
+            false => rest.to_string(),
+        };
+        // "https:evil.example" and "https:///evil.example" have a host too,
+        // file: only after "//"
+        let authority = match scheme.as_deref() {
+            Some("file") => rest.strip_prefix("//"),
+            _ if special => Some(rest.trim_start_matches('/')),
+            _ => rest.strip_prefix("//"),
+        };
+
+        let mut link_url = LinkUrl {
+            raw: raw.to_string(),
+            scheme,
+            user_info: None,
+            host: None,
+            port: None,
+            path: rest.clone(),
+            query,
+            fragment,
+        };
+
+        if let Some(rest) = authority {
+            let authority_end = rest.find('/').unwrap_or(rest.len());
+            let (authority, path) = rest.split_at(authority_end);
+            // "user@host", the last '@' ends the user info
+            let (user_info, host_port) = match authority.rsplit_once('@') {
+                Some((user_info, host_port)) => (Some(user_info.to_string()), host_port),
+                None => (None, authority),
+            };
+            // "[::1]:8080" or "host:8080"
+            let port_start = match host_port.rfind(']') {
+                Some(bracket) => host_port[bracket..].find(':').map(|colon| bracket + colon),
+                None => host_port.rfind(':'),
+            };
+            let (host, port) = match port_start {
+                Some(colon) => (&host_port[..colon], host_port[colon + 1..].parse().ok()),
+                None => (host_port, None),
+            };
+
+            link_url.user_info = user_info;
+            link_url.host = (!host.is_empty()).then(|| host.to_ascii_lowercase());
+            link_url.port = port;
+            link_url.path = path.to_string();
+        }
+
+        link_url
+    }
+
+    pub fn kind(&self) -> UrlKind {
+        match self.scheme.as_deref() {
+            Some("http" | "https") => UrlKind::Http,
+            Some("mailto") => UrlKind::Mailto,
+            Some("file") => UrlKind::File,
+            Some("javascript") => UrlKind::JavaScript,
+            // a drive letter, "C:\..." or "C:/..."
+            Some(scheme) if scheme.len() == 1 && self.path.starts_with(['\\', '/']) => {
+                UrlKind::File
+            }
+            None if self.path.starts_with("\\\\") => UrlKind::File,
+            _ => UrlKind::Other,
+        }
+    }
+
+    // the host, or the domain of a mailto: address
+    pub fn domain(&self) -> Option<String> {
+        match (&self.host, self.kind()) {
+            (Some(host), _) => Some(host.clone()),
+            (None, UrlKind::Mailto) => self
+                .path
+                .split(',')
+                .next()
+                .and_then(|address| address.rsplit_once('@'))
+                .map(|(_, domain)| domain.to_ascii_lowercase()),
+            _ => None,
+        }
+    }
+}
+
+// every distinct URL of the links, in the order they first appear
+pub fn unique_urls(links: &[LinkAnnotation]) -> Vec<&LinkUrl> {
+    let mut urls: Vec<&LinkUrl> = Vec::new();
+    for link in links {
+        if !urls.iter().any(|url| url.raw == link.url.raw) {
+            urls.push(&link.url);
+        }
+    }
+    urls
+}
+
+// "Page  Type          URL" and one "   1  Annotation    url" row per
+// link. Lines that are not rows continue the URL before them. The same URL
+// on the same page is listed once.
+pub(crate) fn parse_urls(stdout: &str) -> Result<Vec<LinkAnnotation>, std::io::Error> {
+    let mut rows: Vec<(u32, String)> = Vec::new();
+    for line in stdout.lines().skip(1) {
+        let row = line
+            .trim_start()
+            .split_once("  Annotation    ")
+            .and_then(|(page, url)| Some((page.parse::<u32>().ok()?, url)));
+        match (row, rows.last_mut()) {
+            (Some((page, url)), _) => rows.push((page, url.to_string())),
+            (None, Some((_, url))) => {
+                url.push('\n');
+                url.push_str(line);
+            }
+            (None, None) if line.trim().is_empty() => {}
+            (None, None) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Unexpected pdfinfo output: {}", line),
+                ))
+            }
+        }
+    }
+
+    let mut links: Vec<LinkAnnotation> = Vec::new();
+    for (page, raw) in rows {
+        let is_duplicate = links
+            .iter()
+            .any(|link| link.page == page && link.url.raw == raw);
+        if !is_duplicate {
+            let url = LinkUrl::parse(&raw);
+            links.push(LinkAnnotation {
+                page,
+                kind: url.kind(),
+                url,
+            });
+        }
+    }
+
+    Ok(links)
+}
@@ -0,0 +1,242 @@
+use super::PdfDate;
+use crate::utils::xml::{XmlEvent, XmlReader};
+use std::collections::HashMap;
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const XML: &str = "http://www.w3.org/XML/1998/namespace";
+const DC: &str = "http://purl.org/dc/elements/1.1/";
+const XMP: &str = "http://ns.adobe.com/xap/1.0/";
+const PDF: &str = "http://ns.adobe.com/pdf/1.3/";
+const PDFAID: &str = "http://www.aiim.org/pdfa/ns/id/";
+
+// the XMP packet of -meta. Language alternatives (title, description,
+// rights) are the x-default entry, or the first one without it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct XmpMetadata {
+    pub title: Option<String>,          // dc:title
+    pub creators: Vec<String>,          // dc:creator
+    pub description: Option<String>,    // dc:description
+    pub subject: Vec<String>,           // dc:subject
+    pub rights: Option<String>,         // dc:rights
+    pub create_date: Option<PdfDate>,   // xmp:CreateDate
+    pub modify_date: Option<PdfDate>,   // xmp:ModifyDate
+    pub metadata_date: Option<PdfDate>, // xmp:MetadataDate
+    pub creator_tool: Option<String>,   // xmp:CreatorTool
+    pub producer: Option<String>,       // pdf:Producer
+    pub keywords: Option<String>,       // pdf:Keywords
+    pub pdfa: Option<PdfAIdentification>,
+    pub raw: String,
+}
+
+// pdfaid:part and pdfaid:conformance, e.g. PDF/A-2b is part 2, "B"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfAIdentification {
+    pub part: Option<u32>, // None when it is not a number
+    pub conformance: Option<String>,
+}
+
+// (namespace, local name) of a property
+type PropertyName = (String, String);
+// (xml:lang, text) of each value, several for rdf:Seq, rdf:Bag and rdf:Alt
+type PropertyValues = Vec<(Option<String>, String)>;
+
+// the property element being read and the rdf:li inside it
+struct OpenProperty {
+    name: PropertyName,
+    depth: usize,
+    lang: Option<String>,
+    text: String,
+    items: PropertyValues,
+    item: Option<(Option<String>, String)>,
+}
+
+pub(crate) fn parse_xmp_metadata(xml: &str) -> Result<XmpMetadata, std::io::Error> {
+    let properties = read_properties(xml)?;
+
+    let values = |namespace: &str, name: &str| {
+        properties
+            .get(&(namespace.to_string(), name.to_string()))
+            .map(|values| values.as_slice())
+            .unwrap_or_default()
+    };
+    let single = |namespace: &str, name: &str| {
+        values(namespace, name)
+            .first()
+            .map(|(_, text)| text.clone())
+    };
+    let list = |namespace: &str, name: &str| {
+        values(namespace, name)
+            .iter()
+            .map(|(_, text)| text.clone())
+            .collect::<Vec<_>>()
+    };
+    let alternative = |namespace: &str, name: &str| {
+        let values = values(namespace, name);
+        values
+            .iter()
+            .find(|(lang, _)| lang.as_deref() == Some("x-default"))
+            .or_else(|| values.first())
+            .map(|(_, text)| text.clone())
+    };
+
+    // a part that is not a number is dropped rather than failing the rest
+    let pdfa = single(PDFAID, "part").map(|part| PdfAIdentification {
+        part: part.trim().parse().ok(),
+        conformance: single(PDFAID, "conformance"),
+    });
+
+    Ok(XmpMetadata {
+        title: alternative(DC, "title"),
+        creators: list(DC, "creator"),
+        description: alternative(DC, "description"),
+        subject: list(DC, "subject"),
+        rights: alternative(DC, "rights"),
+        create_date: single(XMP, "CreateDate").and_then(|date| PdfDate::parse(&date)),
+        modify_date: single(XMP, "ModifyDate").and_then(|date| PdfDate::parse(&date)),
+        metadata_date: single(XMP, "MetadataDate").and_then(|date| PdfDate::parse(&date)),
+        creator_tool: single(XMP, "CreatorTool"),
+        producer: single(PDF, "Producer"),
+        keywords: single(PDF, "Keywords"),
+        pdfa,
+        raw: xml.to_string(),
+    })
+}
+
+// Every property of every rdf:Description, both as child elements and in
+// the attribute shorthand. Nested structures are read as text only.
+fn read_properties(xml: &str) -> Result<HashMap<PropertyName, PropertyValues>, std::io::Error> {
+    let mut properties: HashMap<PropertyName, PropertyValues> = HashMap::new();
+    let mut elements: Vec<PropertyName> = Vec::new();
+    let mut namespaces: Vec<Vec<(String, String)>> = Vec::new();
+    let mut open_property: Option<OpenProperty> = None;
+
+    let mut reader = XmlReader::new(xml);
+    while let Some(event) = reader.next_event()? {
+        match event {
+            XmlEvent::Start { name, attributes } => {
+                namespaces.push(
+                    attributes
+                        .iter()
+                        .filter_map(|(attribute, uri)| {
+                            let prefix = match attribute.as_str() {
+                                "xmlns" => "",
+                                attribute => attribute.strip_prefix("xmlns:")?,
+                            };
+                            Some((prefix.to_string(), uri.clone()))
+                        })
+                        .collect(),
+                );
+                let element = resolve(&namespaces, &name, true);
+                let lang = attributes
+                    .iter()
+                    .find(|(attribute, _)| {
+                        resolve(&namespaces, attribute, false)
+                            == (XML.to_string(), "lang".to_string())
+                    })
+                    .map(|(_, lang)| lang.clone());
+
+                let in_description = elements
+                    .last()
+                    .is_some_and(|parent| is_rdf(parent, "Description"));
+                match open_property.as_mut() {
+                    Some(property) if is_rdf(&element, "li") => {
+                        property.item =
+                            Some((lang.or_else(|| property.lang.clone()), String::new()));
+                    }
+                    Some(_) => {}
+                    None if in_description => {
+                        open_property = Some(OpenProperty {
+                            name: element.clone(),
+                            depth: elements.len(),
+                            lang,
+                            text: String::new(),
+                            items: Vec::new(),
+                            item: None,
+                        });
+                    }
+                    None if is_rdf(&element, "Description") => {
+                        for (attribute, value) in &attributes {
+                            let attribute = resolve(&namespaces, attribute, false);
+                            // xmlns declarations resolve to no namespace
+                            let is_property =
+                                !attribute.0.is_empty() && attribute.0 != RDF && attribute.0 != XML;
+                            if is_property {
+                                properties
+                                    .entry(attribute)
+                                    .or_insert_with(|| vec![(None, value.clone())]);
+                            }
+                        }
+                    }
+                    None => {}
+                }
+
+                elements.push(element);
+            }
+            XmlEvent::Text(text) => {
+                if let Some(property) = open_property.as_mut() {
+                    match property.item.as_mut() {
+                        Some((_, item)) => item.push_str(&text),
+                        None => property.text.push_str(&text),
+                    }
+                }
+            }
+            XmlEvent::End { .. } => {
+                let element = elements.pop().ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "Unexpected XMP: unbalanced end tag",
+                    )
+                })?;
+                namespaces.pop();
+
+                if let Some(property) = open_property.as_mut() {
+                    if is_rdf(&element, "li") {
+                        if let Some((lang, item)) = property.item.take() {
+                            property.items.push((lang, item.trim().to_string()));
+                        }
+                    }
+                }
+                if open_property
+                    .as_ref()
+                    .is_some_and(|property| property.depth == elements.len())
+                {
+                    let property = open_property.take().unwrap();
+                    let mut values = property.items;
+                    let text = property.text.trim();
+                    if values.is_empty() && !text.is_empty() {
+                        values.push((property.lang, text.to_string()));
+                    }
+                    properties.entry(property.name).or_insert(values);
+                }
+            }
+        }
+    }
+
+    Ok(properties)
+}
+
+// "prefix:local" to (namespace, local). Unprefixed attributes have no
+// namespace, unprefixed elements take the default one.
+fn resolve(namespaces: &[Vec<(String, String)>], name: &str, is_element: bool) -> PropertyName {
+    let (prefix, local) = match name.split_once(':') {
+        Some((prefix, local)) => (prefix, local),
+        None if is_element => ("", name),
+        None => return (String::new(), name.to_string()),
+    };
+    if prefix == "xml" {
+        return (XML.to_string(), local.to_string());
+    }
+
+    let namespace = namespaces
+        .iter()
+        .rev()
+        .flatten()
+        .find(|(declared, _)| declared == prefix)
+        .map(|(_, uri)| uri.clone())
+        .unwrap_or_default();
+    (namespace, local.to_string())
+}
+
+fn is_rdf(element: &PropertyName, local: &str) -> bool {
+    element.0 == RDF && element.1 == local
+}
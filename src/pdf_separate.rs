@@ -0,0 +1,92 @@
+use crate::utils::{run_program_with_args, spool_file, PopplerFile, TempDir};
+use std::future::Future;
+
+// -v only prints the version and writes no pages, so it is left out
+pub struct PdfSeparateConfig {
+    pub first_page_to_extract: i32, // firstPageToExtract, -f
+    pub last_page_to_extract: i32,  // lastPageToExtract, -l
+}
+
+impl Default for PdfSeparateConfig {
+    fn default() -> Self {
+        Self {
+            first_page_to_extract: 1,
+            last_page_to_extract: 0, // 0 means all pages
+        }
+    }
+}
+
+// Returns (page number, single page PDF) for every extracted page, in page
+// order. pdfseparate can not read from stdin and only writes files, so both
+// happen in a private temporary directory.
+pub fn pdf_separate(
+    file: PopplerFile,
+    options: PdfSeparateConfig,
+) -> impl Future<Output = Result<Vec<(u32, Vec<u8>)>, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let work_dir = TempDir::new()?;
+        let mut args = parsed_options
+            .into_iter()
+            .map(Into::into)
+            .collect::<Vec<_>>();
+        args.push(spool_file(file, &work_dir, "input.pdf")?);
+        args.push(work_dir.join(&format!("{}%d.pdf", OUTPUT_PREFIX)).into());
+
+        run_program_with_args("pdfseparate", args).await?;
+
+        Ok(collect_pages(work_dir.read_files()?))
+    }
+}
+
+// the page files of the work directory by page number
+pub(crate) fn collect_pages(files: Vec<(String, Vec<u8>)>) -> Vec<(u32, Vec<u8>)> {
+    let mut pages = files
+        .into_iter()
+        .filter_map(|(name, bytes)| Some((parse_page_number(&name)?, bytes)))
+        .collect::<Vec<_>>();
+    // %d is not zero padded, so the names do not sort by page
+    pages.sort_by_key(|(page_number, _)| *page_number);
+    pages
+}
+
+const OUTPUT_PREFIX: &str = "page-";
+
+// page-12.pdf -> 12, anything else in the directory (the spooled input) is skipped
+fn parse_page_number(name: &str) -> Option<u32> {
+    name.strip_prefix(OUTPUT_PREFIX)?
+        .strip_suffix(".pdf")?
+        .parse()
+        .ok()
+}
+
+fn parse_options(options: &PdfSeparateConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(
+        options.first_page_to_extract != 1,
+        "-f",
+        options.first_page_to_extract
+    );
+    add_option!(
+        options.last_page_to_extract != 0,
+        "-l",
+        options.last_page_to_extract
+    );
+
+    parsed_options
+}
@@ -0,0 +1,278 @@
+use crate::utils::{
+    run_program_in_dir, run_program_with_status, spool_file_absolute, PopplerFile, TempDir,
+};
+use std::ffi::OsString;
+use std::future::Future;
+
+#[derive(Default)]
+pub struct PdfSigConfig<'a> {
+    pub nss_dir: &'a str,        // nssDir, -nssdir
+    pub nss_password: &'a str,   // nssPassword, -nss-pwd
+    pub no_cert: bool,           // dontVerifyCert, -nocert
+    pub no_ocsp: bool,           // noOCSPRevocationCheck, -no-ocsp
+    pub aia: bool,               // useAIACertFetch, -aia
+    pub dump: bool,              // dumpSignatures, -dump (fills SignatureInfo::dump)
+    pub owner_password: &'a str, // ownerPassword, -opw
+    pub user_password: &'a str,  // userPassword, -upw
+}
+
+// the sentences pdfsig prints for each SignatureValidationStatus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    Valid,
+    Invalid,
+    DigestMismatch,
+    DecodingError,
+    NotVerified,
+    Unknown(String),
+}
+
+impl SignatureStatus {
+    fn parse(value: &str) -> Self {
+        match value {
+            "Signature is Valid." => SignatureStatus::Valid,
+            "Signature is Invalid." => SignatureStatus::Invalid,
+            "Digest Mismatch." => SignatureStatus::DigestMismatch,
+            "Document isn't signed or corrupted data." => SignatureStatus::DecodingError,
+            "Signature has not yet been verified." => SignatureStatus::NotVerified,
+            value => SignatureStatus::Unknown(value.to_string()),
+        }
+    }
+}
+
+// the sentences pdfsig prints for each CertificateValidationStatus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertificateStatus {
+    Trusted,
+    UntrustedIssuer,
+    UnknownIssuer,
+    Revoked,
+    Expired,
+    NotVerified,
+    Unknown(String),
+}
+
+impl CertificateStatus {
+    fn parse(value: &str) -> Self {
+        match value {
+            "Certificate is Trusted." => CertificateStatus::Trusted,
+            "Certificate issuer isn't Trusted." => CertificateStatus::UntrustedIssuer,
+            "Certificate issuer is unknown." => CertificateStatus::UnknownIssuer,
+            "Certificate has been Revoked." => CertificateStatus::Revoked,
+            "Certificate has Expired" => CertificateStatus::Expired,
+            "Certificate has not yet been verified." => CertificateStatus::NotVerified,
+            value => CertificateStatus::Unknown(value.to_string()),
+        }
+    }
+}
+
+// One "Signature #n:" block. Everything but the number and field name is
+// missing for unsigned signature fields.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignatureInfo {
+    pub number: u32,
+    pub field_name: Option<String>,
+    pub signed: bool,
+    pub signer_common_name: Option<String>,
+    pub signer_distinguished_name: Option<String>,
+    // As pdfsig prints it, strftime "%b %d %Y %H:%M:%S" ("Jan 02 2024
+    // 13:45:10") in the time zone of the machine it ran on, without an offset
+    // and with the month names of the C locale.
+    pub signing_time: Option<String>,
+    pub hash_algorithm: Option<String>, // MD2, MD5, SHA1, SHA-224, SHA-256, SHA-384, SHA-512
+    pub signature_type: Option<String>, // adbe.pkcs7.sha1, adbe.pkcs7.detached, ETSI.CAdES.detached
+    pub signed_ranges: Vec<(u64, u64)>, // byte offsets, [start - end]
+    pub total_document_signed: Option<bool>,
+    pub signature_validation: Option<SignatureStatus>,
+    pub certificate_validation: Option<CertificateStatus>, // only for valid signatures without no_cert
+    pub dump: Option<Vec<u8>>, // raw signature (PKCS#7) with the dump option
+}
+
+impl SignatureInfo {
+    // signed, and the signed ranges reach the end of the file
+    pub fn covers_whole_document(&self) -> bool {
+        self.signed && self.total_document_signed == Some(true)
+    }
+}
+
+pub fn pdf_sig(
+    file: PopplerFile,
+    options: PdfSigConfig<'static>,
+) -> impl Future<Output = Result<Vec<SignatureInfo>, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+    let dump = options.dump;
+
+    async move {
+        // pdfsig can not read from stdin, and -dump writes to the current
+        // directory, where a relative path would no longer be found
+        let work_dir = TempDir::new()?;
+        let input = spool_file_absolute(file, &work_dir, "input.pdf")?;
+
+        let mut args = parsed_options
+            .iter()
+            .map(OsString::from)
+            .collect::<Vec<_>>();
+        args.push(input.clone());
+
+        let output = run_program_with_status("pdfsig", args.clone()).await?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if !output.status.success() {
+            // exits with 2 when there is nothing to report, and for real errors
+            if output.status.code() == Some(2) && stdout.contains("does not contain any signatures")
+            {
+                return Ok(Vec::new());
+            }
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = if stderr.trim().is_empty() {
+                &stdout
+            } else {
+                &stderr
+            };
+            return Err(std::io::Error::other(message.trim().to_string()));
+        }
+        let mut signatures = parse_signatures(&stdout)?;
+
+        if dump {
+            args.insert(0, "-dump".into());
+            run_program_in_dir("pdfsig", args, &work_dir).await?;
+
+            for (name, bytes) in work_dir.read_files()? {
+                if let Some(signature) =
+                    dump_index(&name).and_then(|index| signatures.get_mut(index))
+                {
+                    signature.dump = Some(bytes);
+                }
+            }
+        }
+
+        Ok(signatures)
+    }
+}
+
+// Dumps are written as <input file name>.sig<n>, n counting from 0 and
+// padded with spaces to the width of the signature count, so "input.pdf.sig 3"
+// once there are ten or more.
+pub(crate) fn dump_index(name: &str) -> Option<usize> {
+    let (_, index) = name.rsplit_once(".sig")?;
+    index.trim_start().parse().ok()
+}
+
+fn parse_options(options: &PdfSigConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(!options.nss_dir.is_empty(), "-nssdir", options.nss_dir);
+    add_option!(
+        !options.nss_password.is_empty(),
+        "-nss-pwd",
+        options.nss_password
+    );
+    add_option!(options.no_cert, "-nocert");
+    add_option!(options.no_ocsp, "-no-ocsp");
+    add_option!(options.aia, "-aia");
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+
+    parsed_options
+}
+
+pub(crate) fn parse_signatures(stdout: &str) -> Result<Vec<SignatureInfo>, std::io::Error> {
+    let invalid_data = |line: &str| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Unexpected pdfsig output: {}", line),
+        )
+    };
+
+    let mut signatures: Vec<SignatureInfo> = Vec::new();
+    for line in stdout.lines() {
+        if let Some(number) = line
+            .strip_prefix("Signature #")
+            .and_then(|rest| rest.strip_suffix(':'))
+        {
+            signatures.push(SignatureInfo {
+                number: number.parse().map_err(|_| invalid_data(line))?,
+                // cleared again by "The signature form field is not signed."
+                signed: true,
+                ..SignatureInfo::default()
+            });
+            continue;
+        }
+
+        // anything before the first signature is the "Digital Signature Info of:" header
+        let Some(signature) = signatures.last_mut() else {
+            continue;
+        };
+        let line = line.trim();
+        if line == "The signature form field is not signed." {
+            signature.signed = false;
+            continue;
+        }
+        let Some(line) = line.strip_prefix("- ") else {
+            continue;
+        };
+
+        match line.split_once(": ") {
+            Some(("Signature Field Name", value)) => signature.field_name = Some(value.into()),
+            Some(("Signer Certificate Common Name", value)) => {
+                signature.signer_common_name = Some(value.into())
+            }
+            Some(("Signer full Distinguished Name", value)) => {
+                signature.signer_distinguished_name = Some(value.into())
+            }
+            Some(("Signing Time", value)) => signature.signing_time = Some(value.into()),
+            Some(("Signing Hash Algorithm", value)) => {
+                signature.hash_algorithm = Some(value.into())
+            }
+            Some(("Signature Type", value)) => signature.signature_type = Some(value.into()),
+            Some(("Signed Ranges", value)) => {
+                signature.signed_ranges = parse_ranges(value).ok_or_else(|| invalid_data(line))?
+            }
+            Some(("Signature Validation", value)) => {
+                signature.signature_validation = Some(SignatureStatus::parse(value))
+            }
+            Some(("Certificate Validation", value)) => {
+                signature.certificate_validation = Some(CertificateStatus::parse(value))
+            }
+            _ => match line {
+                "Total document signed" => signature.total_document_signed = Some(true),
+                "Not total document signed" => signature.total_document_signed = Some(false),
+                _ => {}
+            },
+        }
+    }
+
+    Ok(signatures)
+}
+
+// [0 - 1234], [5678 - 9999]
+fn parse_ranges(value: &str) -> Option<Vec<(u64, u64)>> {
+    value
+        .split(',')
+        .map(|range| {
+            let range = range.trim().strip_prefix('[')?.strip_suffix(']')?;
+            let (start, end) = range.split_once(" - ")?;
+            Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+        })
+        .collect()
+}
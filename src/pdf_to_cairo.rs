@@ -0,0 +1,289 @@
+use crate::pdf_to_ppm::{read_rendered_pages, RenderedPage, OUTPUT_ROOT};
+use crate::utils::{run_program_raw, PopplerFile, TempDir};
+use std::future::Future;
+
+// -v only prints the version and renders no pages, so it is left out
+pub struct PdfToCairoConfig<'a> {
+    pub format: CairoFormat,          // pngFile ... svgFile, -png ... -svg
+    pub antialias: &'a str,           // antialias, -antialias
+    pub crop_box: bool,               // cropBox, -cropbox
+    pub crop_height: i32,             // cropHeight, -H
+    pub crop_size: i32,               // cropSize, -sz
+    pub crop_width: i32,              // cropWidth, -W
+    pub crop_x_axis: i32,             // cropXAxis, -x
+    pub crop_y_axis: i32,             // cropYAxis, -y
+    pub duplex: bool,                 // duplex, -duplex
+    pub even_pages_only: bool,        // evenPagesOnly, -e
+    pub fill_page: bool,              // fillPage, -expand
+    pub first_page_to_convert: i32,   // firstPageToConvert, -f
+    pub grayscale_file: bool,         // grayscaleFile, -gray
+    pub icc_file: &'a str,            // iccFile, -icc
+    pub jpeg_options: &'a str,        // jpegOptions, -jpegopt
+    pub last_page_to_convert: i32,    // lastPageToConvert, -l
+    pub monochrome_file: bool,        // monochromeFile, -mono
+    pub no_center: bool,              // noCenter, -nocenter
+    pub no_crop: bool,                // noCrop, -nocrop
+    pub no_shrink: bool,              // noShrink, -noshrink
+    pub odd_pages_only: bool,         // oddPagesOnly, -o
+    pub original_page_sizes: bool,    // originalPageSizes, -origpagesizes
+    pub owner_password: &'a str,      // ownerPassword, -opw
+    pub paper_height: i32,            // paperHeight, -paperh
+    pub paper_size: &'a str,          // paperSize, -paper
+    pub paper_width: i32,             // paperWidth, -paperw
+    pub print_doc_struct: bool,       // printDocStruct, -struct
+    pub ps_level2: bool,              // psLevel2, -level2
+    pub ps_level3: bool,              // psLevel3, -level3
+    pub quiet: bool,                  // quiet, -q
+    pub resolution_x_axis: f64,       // resolutionXAxis, -rx
+    pub resolution_xy_axis: f64,      // resolutionXYAxis, -r
+    pub resolution_y_axis: f64,       // resolutionYAxis, -ry
+    pub scale_page_to: i32,           // scalePageTo, -scale-to
+    pub scale_page_to_x_axis: i32,    // scalePageToXAxis, -scale-to-x
+    pub scale_page_to_y_axis: i32,    // scalePageToYAxis, -scale-to-y
+    pub single_file: bool,            // singleFile, -singlefile
+    pub tiff_compression: &'a str,    // tiffCompression, -tiffcompression
+    pub transparent_page_color: bool, // transparentPageColor, -transp
+    pub user_password: &'a str,       // userPassword, -upw
+}
+
+impl<'a> Default for PdfToCairoConfig<'a> {
+    fn default() -> Self {
+        Self {
+            format: CairoFormat::Png,
+            antialias: "", // default, none, gray, subpixel, fast, good, best
+            crop_box: false,
+            crop_height: 0, // in pixels (image output) or points (vector output)
+            crop_size: 0,   // sets both crop_width and crop_height
+            crop_width: 0,
+            crop_x_axis: 0,
+            crop_y_axis: 0,
+            duplex: false, // ps only
+            even_pages_only: false,
+            fill_page: false, // expand pages smaller than the paper size
+            first_page_to_convert: 1,
+            grayscale_file: false,   // png, jpeg, tiff only
+            icc_file: "",            // path to an ICC profile, needs poppler built with lcms
+            jpeg_options: "",        // quality=n,progressive=y|n,optimize=y|n
+            last_page_to_convert: 0, // 0 means all pages
+            monochrome_file: false,  // png, jpeg, tiff only
+            no_center: false,
+            no_crop: false,
+            no_shrink: false,
+            odd_pages_only: false,
+            original_page_sizes: false, // pdf, ps, eps, svg only
+            owner_password: "",
+            paper_height: 0,         // in points
+            paper_size: "",          // letter, legal, A4, A3, match
+            paper_width: 0,          // in points
+            print_doc_struct: false, // pdf only, needs cairo 1.18
+            ps_level2: false,
+            ps_level3: false,
+            quiet: false,
+            resolution_x_axis: 150.0,      // in dpi
+            resolution_xy_axis: 0.0,       // in dpi, >0 overrides both axes
+            resolution_y_axis: 150.0,      // in dpi
+            scale_page_to: 0,              // in pixels, long side of the page
+            scale_page_to_x_axis: 0,       // in pixels, -1 keeps the aspect ratio
+            scale_page_to_y_axis: 0,       // in pixels, -1 keeps the aspect ratio
+            single_file: false,            // first page only
+            tiff_compression: "",          // none, packbits, jpeg, lzw, deflate
+            transparent_page_color: false, // png and tiff only
+            user_password: "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CairoFormat {
+    Png,
+    Jpeg,
+    Tiff,
+    Pdf,
+    Ps,
+    Eps,
+    Svg,
+}
+
+impl CairoFormat {
+    fn as_arg(&self) -> &'static str {
+        match self {
+            CairoFormat::Png => "-png",
+            CairoFormat::Jpeg => "-jpeg",
+            CairoFormat::Tiff => "-tiff",
+            CairoFormat::Pdf => "-pdf",
+            CairoFormat::Ps => "-ps",
+            CairoFormat::Eps => "-eps",
+            CairoFormat::Svg => "-svg",
+        }
+    }
+
+    // vector formats are a single document that pdftocairo can write to stdout
+    pub fn is_vector(&self) -> bool {
+        matches!(
+            self,
+            CairoFormat::Pdf | CairoFormat::Ps | CairoFormat::Eps | CairoFormat::Svg
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CairoOutput {
+    Pages(Vec<RenderedPage>), // png, jpeg, tiff
+    Document(Vec<u8>),        // pdf, ps, eps, svg
+}
+
+pub fn pdf_to_cairo(
+    file: PopplerFile,
+    options: PdfToCairoConfig<'static>,
+) -> impl Future<Output = Result<CairoOutput, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+    let format = options.format;
+    let first_page = options.first_page_to_convert.max(1) as u32;
+
+    async move {
+        // images are one file per page, so collect them from a private directory
+        let output_dir = TempDir::new()?;
+        let output_file = output_args(format, &output_dir);
+        let stdout = run_program_raw(file, "pdftocairo", parsed_options, output_file).await?;
+
+        let files = if format.is_vector() {
+            Vec::new()
+        } else {
+            output_dir.read_files()?
+        };
+        collect_output(format, stdout, files, first_page)
+    }
+}
+
+// vector formats go to stdout, images to one file per page under output_dir
+pub(crate) fn output_args(format: CairoFormat, output_dir: &TempDir) -> Vec<String> {
+    if format.is_vector() {
+        vec!["-".to_string()]
+    } else {
+        vec![output_dir.join(OUTPUT_ROOT)]
+    }
+}
+
+pub(crate) fn collect_output(
+    format: CairoFormat,
+    stdout: Vec<u8>,
+    files: Vec<(String, Vec<u8>)>,
+    first_page: u32,
+) -> Result<CairoOutput, std::io::Error> {
+    if format.is_vector() {
+        return Ok(CairoOutput::Document(stdout));
+    }
+    Ok(CairoOutput::Pages(read_rendered_pages(files, first_page)?))
+}
+
+fn parse_options(options: &PdfToCairoConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(true, options.format.as_arg());
+    add_option!(
+        !options.antialias.is_empty(),
+        "-antialias",
+        options.antialias
+    );
+    add_option!(options.crop_box, "-cropbox");
+    add_option!(options.crop_height != 0, "-H", options.crop_height);
+    add_option!(options.crop_size != 0, "-sz", options.crop_size);
+    add_option!(options.crop_width != 0, "-W", options.crop_width);
+    add_option!(options.crop_x_axis != 0, "-x", options.crop_x_axis);
+    add_option!(options.crop_y_axis != 0, "-y", options.crop_y_axis);
+    add_option!(options.duplex, "-duplex");
+    add_option!(options.even_pages_only, "-e");
+    add_option!(options.fill_page, "-expand");
+    add_option!(
+        options.first_page_to_convert != 1,
+        "-f",
+        options.first_page_to_convert
+    );
+    add_option!(options.grayscale_file, "-gray");
+    add_option!(!options.icc_file.is_empty(), "-icc", options.icc_file);
+    add_option!(
+        !options.jpeg_options.is_empty(),
+        "-jpegopt",
+        options.jpeg_options
+    );
+    add_option!(
+        options.last_page_to_convert != 0,
+        "-l",
+        options.last_page_to_convert
+    );
+    add_option!(options.monochrome_file, "-mono");
+    add_option!(options.no_center, "-nocenter");
+    add_option!(options.no_crop, "-nocrop");
+    add_option!(options.no_shrink, "-noshrink");
+    add_option!(options.odd_pages_only, "-o");
+    add_option!(options.original_page_sizes, "-origpagesizes");
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(options.paper_height != 0, "-paperh", options.paper_height);
+    add_option!(!options.paper_size.is_empty(), "-paper", options.paper_size);
+    add_option!(options.paper_width != 0, "-paperw", options.paper_width);
+    add_option!(options.print_doc_struct, "-struct");
+    add_option!(options.ps_level2, "-level2");
+    add_option!(options.ps_level3, "-level3");
+    add_option!(options.quiet, "-q");
+    add_option!(
+        options.resolution_x_axis != 150.0,
+        "-rx",
+        options.resolution_x_axis
+    );
+    add_option!(
+        options.resolution_xy_axis != 0.0,
+        "-r",
+        options.resolution_xy_axis
+    );
+    add_option!(
+        options.resolution_y_axis != 150.0,
+        "-ry",
+        options.resolution_y_axis
+    );
+    add_option!(
+        options.scale_page_to != 0,
+        "-scale-to",
+        options.scale_page_to
+    );
+    add_option!(
+        options.scale_page_to_x_axis != 0,
+        "-scale-to-x",
+        options.scale_page_to_x_axis
+    );
+    add_option!(
+        options.scale_page_to_y_axis != 0,
+        "-scale-to-y",
+        options.scale_page_to_y_axis
+    );
+    add_option!(options.single_file, "-singlefile");
+    add_option!(
+        !options.tiff_compression.is_empty(),
+        "-tiffcompression",
+        options.tiff_compression
+    );
+    add_option!(options.transparent_page_color, "-transp");
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+
+    parsed_options
+}
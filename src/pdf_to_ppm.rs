@@ -0,0 +1,428 @@
+use crate::utils::{run_program_raw, PopplerFile, TempDir};
+use std::future::Future;
+
+// -v only prints the version and renders no pages, so it is left out
+pub struct PdfToPpmConfig<'a> {
+    pub antialias_fonts: &'a str,      // antialiasFonts, -aa
+    pub antialias_vectors: &'a str,    // antialiasVectors, -aaVector
+    pub crop_box: bool,                // cropBox, -cropbox
+    pub crop_height: i32,              // cropHeight, -H
+    pub crop_size: i32,                // cropSize, -sz
+    pub crop_width: i32,               // cropWidth, -W
+    pub crop_x_axis: i32,              // cropXAxis, -x
+    pub crop_y_axis: i32,              // cropYAxis, -y
+    pub default_cmyk_profile: &'a str, // defaultCmykProfile, -defaultcmykprofile
+    pub default_gray_profile: &'a str, // defaultGrayProfile, -defaultgrayprofile
+    pub default_rgb_profile: &'a str,  // defaultRgbProfile, -defaultrgbprofile
+    pub display_profile: &'a str,      // displayProfile, -displayprofile
+    pub even_pages_only: bool,         // evenPagesOnly, -e
+    pub first_page_to_convert: i32,    // firstPageToConvert, -f
+    pub freetype: &'a str,             // freetype, -freetype
+    pub force_page_number: bool,       // forcePageNumber, -forcenum
+    pub grayscale_file: bool,          // grayscaleFile, -gray
+    pub hide_annotations: bool,        // hideAnnotations, -hide-annotations
+    pub jpeg_file: bool,               // jpegFile, -jpeg
+    pub jpeg_options: &'a str,         // jpegOpt, -jpegopt
+    pub last_page_to_convert: i32,     // lastPageToConvert, -l
+    pub monochrome_file: bool,         // monochromeFile, -mono
+    pub odd_pages_only: bool,          // oddPagesOnly, -o
+    pub owner_password: &'a str,       // ownerPassword, -opw
+    pub png_file: bool,                // pngFile, -png
+    pub print_progress: bool,          // printProgress, -progress
+    pub quiet: bool,                   // quiet, -q
+    pub resolution_x_axis: f64,        // resolutionXAxis, -rx
+    pub resolution_xy_axis: f64,       // resolutionXYAxis, -r
+    pub resolution_y_axis: f64,        // resolutionYAxis, -ry
+    pub scale_page_to: i32,            // scalePageTo, -scale-to
+    pub scale_page_to_x_axis: i32,     // scalePageToXAxis, -scale-to-x
+    pub scale_page_to_y_axis: i32,     // scalePageToYAxis, -scale-to-y
+    pub separator: &'a str,            // separator, -sep
+    pub single_file: bool,             // singleFile, -singlefile
+    pub thin_line_mode: &'a str,       // thinLineMode, -thinlinemode
+    pub tiff_compression: &'a str,     // tiffCompression, -tiffcompression
+    pub tiff_file: bool,               // tiffFile, -tiff
+    pub user_password: &'a str,        // userPassword, -upw
+}
+
+impl<'a> Default for PdfToPpmConfig<'a> {
+    fn default() -> Self {
+        Self {
+            antialias_fonts: "",   // yes, no (default yes)
+            antialias_vectors: "", // yes, no (default yes)
+            crop_box: false,
+            crop_height: 0, // in pixels
+            crop_size: 0,   // in pixels, sets both crop_width and crop_height
+            crop_width: 0,  // in pixels
+            crop_x_axis: 0,
+            crop_y_axis: 0,
+            default_cmyk_profile: "", // path to an ICC profile, needs poppler built with lcms
+            default_gray_profile: "",
+            default_rgb_profile: "",
+            display_profile: "",
+            even_pages_only: false,
+            first_page_to_convert: 1,
+            freetype: "", // yes, no (default yes)
+            force_page_number: false,
+            grayscale_file: false, // pgm
+            hide_annotations: false,
+            jpeg_file: false,
+            jpeg_options: "",        // quality=n,progressive=y|n,optimize=y|n
+            last_page_to_convert: 0, // 0 means all pages
+            monochrome_file: false,  // pbm
+            odd_pages_only: false,
+            owner_password: "",
+            png_file: false,
+            print_progress: false, // progress is written to stderr
+            quiet: false,
+            resolution_x_axis: 150.0, // in dpi
+            resolution_xy_axis: 0.0,  // in dpi, >0 overrides both axes
+            resolution_y_axis: 150.0, // in dpi
+            scale_page_to: 0,         // in pixels, long side of the page
+            scale_page_to_x_axis: 0,  // in pixels, -1 keeps the aspect ratio
+            scale_page_to_y_axis: 0,  // in pixels, -1 keeps the aspect ratio
+            separator: "",            // single character (default -)
+            single_file: false,       // first page only
+            thin_line_mode: "",       // none, solid, shape (default none)
+            tiff_compression: "",     // none, packbits, jpeg, lzw, deflate
+            tiff_file: false,
+            user_password: "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Pgm,
+    Pbm,
+    Png,
+    Jpeg,
+    Tiff,
+}
+
+impl ImageFormat {
+    fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "ppm" => Some(ImageFormat::Ppm),
+            "pgm" => Some(ImageFormat::Pgm),
+            "pbm" => Some(ImageFormat::Pbm),
+            "png" => Some(ImageFormat::Png),
+            "jpg" => Some(ImageFormat::Jpeg),
+            "tif" => Some(ImageFormat::Tiff),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedPage {
+    pub page_number: u32,
+    pub format: ImageFormat,
+    pub width: u32,  // in pixels
+    pub height: u32, // in pixels
+    pub bytes: Vec<u8>,
+}
+
+pub fn pdf_to_ppm(
+    file: PopplerFile,
+    options: PdfToPpmConfig<'static>,
+) -> impl Future<Output = Result<Vec<RenderedPage>, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+    let first_page = options.first_page_to_convert.max(1) as u32;
+
+    async move {
+        // pdftoppm only writes files, so collect them from a private directory
+        let output_dir = TempDir::new()?;
+        let output_prefix = vec![output_dir.join(OUTPUT_ROOT)];
+        run_program_raw(file, "pdftoppm", parsed_options, output_prefix).await?;
+
+        read_rendered_pages(output_dir.read_files()?, first_page)
+    }
+}
+
+// file name prefix handed to poppler, pages come back as page-1.ppm, page-01.png, ...
+pub(crate) const OUTPUT_ROOT: &str = "page";
+
+// The files poppler wrote, by page number. The numbers are zero padded to
+// the width of the last page, but sorting by name would still put a file
+// without padding out of place.
+pub(crate) fn read_rendered_pages(
+    files: Vec<(String, Vec<u8>)>,
+    first_page: u32,
+) -> Result<Vec<RenderedPage>, std::io::Error> {
+    let mut pages = files
+        .into_iter()
+        .map(|(name, bytes)| read_rendered_page(&name, bytes, first_page))
+        .collect::<Result<Vec<_>, _>>()?;
+    pages.sort_by_key(|page| page.page_number);
+    Ok(pages)
+}
+
+// single file output has no page number in the name, so it is the first page
+pub(crate) fn read_rendered_page(
+    name: &str,
+    bytes: Vec<u8>,
+    first_page: u32,
+) -> Result<RenderedPage, std::io::Error> {
+    let invalid_data = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Unexpected output file: {}", name),
+        )
+    };
+
+    let (stem, extension) = name.rsplit_once('.').ok_or_else(invalid_data)?;
+    let format = ImageFormat::from_extension(extension).ok_or_else(invalid_data)?;
+    let page_number = match stem.strip_prefix(OUTPUT_ROOT).ok_or_else(invalid_data)? {
+        "" => first_page,
+        // skip the separator, whatever character it is
+        number => number
+            .get(1..)
+            .and_then(|number| number.parse().ok())
+            .ok_or_else(invalid_data)?,
+    };
+    let (width, height) = image_dimensions(format, &bytes).ok_or_else(invalid_data)?;
+
+    Ok(RenderedPage {
+        page_number,
+        format,
+        width,
+        height,
+        bytes,
+    })
+}
+
+// reads the pixel size from the image header
+pub(crate) fn image_dimensions(format: ImageFormat, bytes: &[u8]) -> Option<(u32, u32)> {
+    match format {
+        ImageFormat::Ppm | ImageFormat::Pgm | ImageFormat::Pbm => pnm_dimensions(bytes),
+        ImageFormat::Png => png_dimensions(bytes),
+        ImageFormat::Jpeg => jpeg_dimensions(bytes),
+        ImageFormat::Tiff => tiff_dimensions(bytes),
+    }
+}
+
+// P4/P5/P6, whitespace, width, whitespace, height (comments start with #)
+fn pnm_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let header = String::from_utf8_lossy(bytes.get(..bytes.len().min(512))?);
+    let mut tokens = header
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or(""))
+        .flat_map(|line| line.split_whitespace());
+
+    if !tokens.next()?.starts_with('P') {
+        return None;
+    }
+    Some((tokens.next()?.parse().ok()?, tokens.next()?.parse().ok()?))
+}
+
+// the IHDR chunk always comes first, right after the 8 byte signature
+fn png_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+    let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+    Some((width, height))
+}
+
+// walks the marker segments until the start of frame
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let read_u16 = |offset: usize| -> Option<usize> {
+        Some(u16::from_be_bytes(bytes.get(offset..offset + 2)?.try_into().ok()?) as usize)
+    };
+
+    let mut offset = 2;
+    loop {
+        if *bytes.get(offset)? != 0xFF {
+            return None;
+        }
+        let marker = *bytes.get(offset + 1)?;
+        match marker {
+            // SOF0 - SOF15, except DHT, JPG and DAC which share the range
+            0xC0..=0xCF if marker != 0xC4 && marker != 0xC8 && marker != 0xCC => {
+                let height = read_u16(offset + 5)?;
+                let width = read_u16(offset + 7)?;
+                return Some((width as u32, height as u32));
+            }
+            _ => offset += 2 + read_u16(offset + 2)?,
+        }
+    }
+}
+
+// ImageWidth (256) and ImageLength (257) from the first IFD
+fn tiff_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let big_endian = match bytes.get(..2)? {
+        b"II" => false,
+        b"MM" => true,
+        _ => return None,
+    };
+    let read_u16 = |offset: usize| -> Option<u32> {
+        let value = bytes.get(offset..offset + 2)?.try_into().ok()?;
+        Some(if big_endian {
+            u16::from_be_bytes(value)
+        } else {
+            u16::from_le_bytes(value)
+        } as u32)
+    };
+    let read_u32 = |offset: usize| -> Option<u32> {
+        let value = bytes.get(offset..offset + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(value)
+        } else {
+            u32::from_le_bytes(value)
+        })
+    };
+
+    let ifd = read_u32(4)? as usize;
+    let (mut width, mut height) = (None, None);
+    for entry in 0..read_u16(ifd)? as usize {
+        let entry = ifd + 2 + entry * 12;
+        // SHORT (3) or LONG (4) values are stored inline
+        let value = match read_u16(entry + 2)? {
+            3 => read_u16(entry + 8)?,
+            _ => read_u32(entry + 8)?,
+        };
+        match read_u16(entry)? {
+            256 => width = Some(value),
+            257 => height = Some(value),
+            _ => {}
+        }
+    }
+    Some((width?, height?))
+}
+
+fn parse_options(options: &PdfToPpmConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(
+        !options.antialias_fonts.is_empty(),
+        "-aa",
+        options.antialias_fonts
+    );
+    add_option!(
+        !options.antialias_vectors.is_empty(),
+        "-aaVector",
+        options.antialias_vectors
+    );
+    add_option!(options.crop_box, "-cropbox");
+    add_option!(options.crop_height != 0, "-H", options.crop_height);
+    add_option!(options.crop_size != 0, "-sz", options.crop_size);
+    add_option!(options.crop_width != 0, "-W", options.crop_width);
+    add_option!(options.crop_x_axis != 0, "-x", options.crop_x_axis);
+    add_option!(options.crop_y_axis != 0, "-y", options.crop_y_axis);
+    add_option!(
+        !options.default_cmyk_profile.is_empty(),
+        "-defaultcmykprofile",
+        options.default_cmyk_profile
+    );
+    add_option!(
+        !options.default_gray_profile.is_empty(),
+        "-defaultgrayprofile",
+        options.default_gray_profile
+    );
+    add_option!(
+        !options.default_rgb_profile.is_empty(),
+        "-defaultrgbprofile",
+        options.default_rgb_profile
+    );
+    add_option!(
+        !options.display_profile.is_empty(),
+        "-displayprofile",
+        options.display_profile
+    );
+    add_option!(options.even_pages_only, "-e");
+    add_option!(
+        options.first_page_to_convert != 1,
+        "-f",
+        options.first_page_to_convert
+    );
+    add_option!(!options.freetype.is_empty(), "-freetype", options.freetype);
+    add_option!(options.force_page_number, "-forcenum");
+    add_option!(options.grayscale_file, "-gray");
+    add_option!(options.hide_annotations, "-hide-annotations");
+    add_option!(options.jpeg_file, "-jpeg");
+    add_option!(
+        !options.jpeg_options.is_empty(),
+        "-jpegopt",
+        options.jpeg_options
+    );
+    add_option!(
+        options.last_page_to_convert != 0,
+        "-l",
+        options.last_page_to_convert
+    );
+    add_option!(options.monochrome_file, "-mono");
+    add_option!(options.odd_pages_only, "-o");
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(options.png_file, "-png");
+    add_option!(options.print_progress, "-progress");
+    add_option!(options.quiet, "-q");
+    add_option!(
+        options.resolution_x_axis != 150.0,
+        "-rx",
+        options.resolution_x_axis
+    );
+    add_option!(
+        options.resolution_xy_axis != 0.0,
+        "-r",
+        options.resolution_xy_axis
+    );
+    add_option!(
+        options.resolution_y_axis != 150.0,
+        "-ry",
+        options.resolution_y_axis
+    );
+    add_option!(
+        options.scale_page_to != 0,
+        "-scale-to",
+        options.scale_page_to
+    );
+    add_option!(
+        options.scale_page_to_x_axis != 0,
+        "-scale-to-x",
+        options.scale_page_to_x_axis
+    );
+    add_option!(
+        options.scale_page_to_y_axis != 0,
+        "-scale-to-y",
+        options.scale_page_to_y_axis
+    );
+    add_option!(!options.separator.is_empty(), "-sep", options.separator);
+    add_option!(options.single_file, "-singlefile");
+    add_option!(
+        !options.thin_line_mode.is_empty(),
+        "-thinlinemode",
+        options.thin_line_mode
+    );
+    add_option!(
+        !options.tiff_compression.is_empty(),
+        "-tiffcompression",
+        options.tiff_compression
+    );
+    add_option!(options.tiff_file, "-tiff");
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+
+    parsed_options
+}
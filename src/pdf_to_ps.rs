@@ -0,0 +1,236 @@
+use crate::utils::{run_program_raw, PopplerFile};
+use std::future::Future;
+
+// -v only prints the version and writes no PostScript, so it is left out
+pub struct PdfToPsConfig<'a> {
+    pub antialias: &'a str,                 // antialias, -aaRaster
+    pub binary: bool,                       // binary, -binary
+    pub default_cmyk_profile: &'a str,      // defaultCmykProfile, -defaultcmykprofile
+    pub default_gray_profile: &'a str,      // defaultGrayProfile, -defaultgrayprofile
+    pub default_rgb_profile: &'a str,       // defaultRgbProfile, -defaultrgbprofile
+    pub duplex: bool,                       // duplex, -duplex
+    pub eps_file: bool,                     // epsFile, -eps
+    pub fill_page: bool,                    // fillPage, -expand
+    pub first_page_to_convert: i32,         // firstPageToConvert, -f
+    pub form: bool,                         // form, -form
+    pub last_page_to_convert: i32,          // lastPageToConvert, -l
+    pub level: PsLevel,                     // level1 ... level3Sep, -level1 ... -level3sep
+    pub no_embed_cid_fonts: bool,           // noEmbedCIDFonts, -noembcidps
+    pub no_embed_cid_true_type_fonts: bool, // noEmbedCIDTrueTypeFonts, -noembcidtt
+    pub no_embed_true_type_fonts: bool,     // noEmbedTrueTypeFonts, -noembtt
+    pub no_embed_type1_fonts: bool,         // noEmbedType1Fonts, -noembt1
+    pub no_center: bool,                    // noCenter, -nocenter
+    pub no_crop: bool,                      // noCrop, -nocrop
+    pub no_shrink: bool,                    // noShrink, -noshrink
+    pub opi: bool,                          // opi, -opi
+    pub optimize_color_space: bool,         // optimizecolorspace, -optimizecolorspace
+    pub original_page_sizes: bool,          // originalPageSizes, -origpagesizes
+    pub overprint: bool,                    // overprint, -overprint
+    pub owner_password: &'a str,            // ownerPassword, -opw
+    pub paper_height: i32,                  // paperHeight, -paperh
+    pub paper_size: &'a str,                // paperSize, -paper
+    pub paper_width: i32,                   // paperWidth, -paperw
+    pub pass_fonts: bool,                   // passfonts, -passfonts
+    pub preload: bool,                      // preload, -preload
+    pub process_color_format: &'a str,      // processColorFormat, -processcolorformat
+    pub process_color_profile: &'a str,     // processColorProfile, -processcolorprofile
+    pub quiet: bool,                        // quiet, -q
+    pub rasterize: RasterizeMode,           // rasterize, -rasterize
+    pub resolution_xy_axis: i32,            // resolutionXYAxis, -r
+    pub user_password: &'a str,             // userPassword, -upw
+}
+
+impl<'a> Default for PdfToPsConfig<'a> {
+    fn default() -> Self {
+        Self {
+            antialias: "",            // yes, no (default no)
+            binary: false,            // level 1 only
+            default_cmyk_profile: "", // path to an ICC profile, needs poppler built with lcms
+            default_gray_profile: "",
+            default_rgb_profile: "",
+            duplex: false,
+            eps_file: false,  // single page only
+            fill_page: false, // expand pages smaller than the paper size
+            first_page_to_convert: 1,
+            form: false,             // single page only
+            last_page_to_convert: 0, // 0 means all pages
+            level: PsLevel::Level2,
+            no_embed_cid_fonts: false,
+            no_embed_cid_true_type_fonts: false,
+            no_embed_true_type_fonts: false,
+            no_embed_type1_fonts: false,
+            no_center: false,
+            no_crop: false,
+            no_shrink: false,
+            opi: false, // level 2 and 3 only
+            optimize_color_space: false,
+            original_page_sizes: false,
+            overprint: false,
+            owner_password: "",
+            paper_height: 0, // in points
+            paper_size: "",  // letter, legal, A4, A3, match
+            paper_width: 0,  // in points
+            pass_fonts: false,
+            preload: false,
+            process_color_format: "",  // MONO8, RGB8, CMYK8
+            process_color_profile: "", // path to an ICC profile
+            quiet: false,
+            rasterize: RasterizeMode::WhenNeeded,
+            resolution_xy_axis: 300, // in dpi, used when rasterizing
+            user_password: "",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PsLevel {
+    Level1,
+    Level1Sep,
+    Level2,
+    Level2Sep,
+    Level3,
+    Level3Sep,
+}
+
+impl PsLevel {
+    fn as_arg(&self) -> &'static str {
+        match self {
+            PsLevel::Level1 => "-level1",
+            PsLevel::Level1Sep => "-level1sep",
+            PsLevel::Level2 => "-level2",
+            PsLevel::Level2Sep => "-level2sep",
+            PsLevel::Level3 => "-level3",
+            PsLevel::Level3Sep => "-level3sep",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterizeMode {
+    Always,
+    Never,
+    WhenNeeded,
+}
+
+impl RasterizeMode {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RasterizeMode::Always => "always",
+            RasterizeMode::Never => "never",
+            RasterizeMode::WhenNeeded => "whenneeded",
+        }
+    }
+}
+
+pub fn pdf_to_ps(
+    file: PopplerFile,
+    options: PdfToPsConfig<'static>,
+) -> impl Future<Output = Result<Vec<u8>, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+
+    // "-" writes the PostScript to stdout
+    run_program_raw(file, "pdftops", parsed_options, vec!["-".to_string()])
+}
+
+pub(crate) fn parse_options(options: &PdfToPsConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(
+        !options.antialias.is_empty(),
+        "-aaRaster",
+        options.antialias
+    );
+    add_option!(options.binary, "-binary");
+    add_option!(
+        !options.default_cmyk_profile.is_empty(),
+        "-defaultcmykprofile",
+        options.default_cmyk_profile
+    );
+    add_option!(
+        !options.default_gray_profile.is_empty(),
+        "-defaultgrayprofile",
+        options.default_gray_profile
+    );
+    add_option!(
+        !options.default_rgb_profile.is_empty(),
+        "-defaultrgbprofile",
+        options.default_rgb_profile
+    );
+    add_option!(options.duplex, "-duplex");
+    add_option!(options.eps_file, "-eps");
+    add_option!(options.fill_page, "-expand");
+    add_option!(
+        options.first_page_to_convert != 1,
+        "-f",
+        options.first_page_to_convert
+    );
+    add_option!(options.form, "-form");
+    add_option!(
+        options.last_page_to_convert != 0,
+        "-l",
+        options.last_page_to_convert
+    );
+    add_option!(options.level != PsLevel::Level2, options.level.as_arg());
+    add_option!(options.no_embed_cid_fonts, "-noembcidps");
+    add_option!(options.no_embed_cid_true_type_fonts, "-noembcidtt");
+    add_option!(options.no_embed_true_type_fonts, "-noembtt");
+    add_option!(options.no_embed_type1_fonts, "-noembt1");
+    add_option!(options.no_center, "-nocenter");
+    add_option!(options.no_crop, "-nocrop");
+    add_option!(options.no_shrink, "-noshrink");
+    add_option!(options.opi, "-opi");
+    add_option!(options.optimize_color_space, "-optimizecolorspace");
+    add_option!(options.original_page_sizes, "-origpagesizes");
+    add_option!(options.overprint, "-overprint");
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(options.paper_height != 0, "-paperh", options.paper_height);
+    add_option!(!options.paper_size.is_empty(), "-paper", options.paper_size);
+    add_option!(options.paper_width != 0, "-paperw", options.paper_width);
+    add_option!(options.pass_fonts, "-passfonts");
+    add_option!(options.preload, "-preload");
+    add_option!(
+        !options.process_color_format.is_empty(),
+        "-processcolorformat",
+        options.process_color_format
+    );
+    add_option!(
+        !options.process_color_profile.is_empty(),
+        "-processcolorprofile",
+        options.process_color_profile
+    );
+    add_option!(options.quiet, "-q");
+    add_option!(
+        options.rasterize != RasterizeMode::WhenNeeded,
+        "-rasterize",
+        options.rasterize.as_str()
+    );
+    add_option!(
+        options.resolution_xy_axis != 300,
+        "-r",
+        options.resolution_xy_axis
+    );
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+
+    parsed_options
+}
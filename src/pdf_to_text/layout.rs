@@ -0,0 +1,190 @@
+use super::words::{number_attribute, rect_attributes, unexpected_output};
+use super::Word;
+use crate::utils::xml::{XmlEvent, XmlReader};
+use crate::utils::Rect;
+
+// The -bbox-layout output as a tree, everything in reading order and all
+// boxes counting y down from the top of the page.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayoutDocument {
+    pub pages: Vec<LayoutPage>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutPage {
+    pub number: u32,
+    pub width: f64,
+    pub height: f64,
+    pub flows: Vec<LayoutFlow>,
+}
+
+// A column or other run of blocks that reads on from one to the next.
+// pdftotext gives flows no box, rect is the one around its blocks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutFlow {
+    pub rect: Rect,
+    pub blocks: Vec<LayoutBlock>,
+}
+
+// a paragraph, or whatever else pdftotext sets apart
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutBlock {
+    pub rect: Rect,
+    pub lines: Vec<LayoutLine>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    pub rect: Rect,
+    pub words: Vec<Word>,
+}
+
+impl LayoutDocument {
+    pub fn blocks(&self) -> impl Iterator<Item = &LayoutBlock> {
+        self.pages.iter().flat_map(LayoutPage::blocks)
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &LayoutLine> {
+        self.pages.iter().flat_map(LayoutPage::lines)
+    }
+
+    // the pages separated by form feeds, as pdftotext writes plain text
+    pub fn text(&self) -> String {
+        join(self.pages.iter().map(LayoutPage::text), "\x0c")
+    }
+}
+
+impl LayoutPage {
+    // the whole page
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x_min: 0.0,
+            y_min: 0.0,
+            x_max: self.width,
+            y_max: self.height,
+        }
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = &LayoutBlock> {
+        self.flows.iter().flat_map(|flow| flow.blocks.iter())
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &LayoutLine> {
+        self.blocks().flat_map(|block| block.lines.iter())
+    }
+
+    pub fn text(&self) -> String {
+        join(self.flows.iter().map(LayoutFlow::text), "\n\n")
+    }
+}
+
+impl LayoutFlow {
+    pub fn text(&self) -> String {
+        join(self.blocks.iter().map(LayoutBlock::text), "\n\n")
+    }
+}
+
+impl LayoutBlock {
+    // one line of text per line
+    pub fn text(&self) -> String {
+        join(self.lines.iter().map(LayoutLine::text), "\n")
+    }
+}
+
+impl LayoutLine {
+    // the words separated by single spaces
+    pub fn text(&self) -> String {
+        join(self.words.iter().map(|word| word.text.clone()), " ")
+    }
+}
+
+fn join(parts: impl Iterator<Item = String>, separator: &str) -> String {
+    parts.collect::<Vec<_>>().join(separator)
+}
+
+// <page width height>, <flow>, <block xMin yMin xMax yMax>, <line ...> and
+// <word ...>text</word> nested in that order. The pages count up from
+// first_page.
+pub(crate) fn parse_layout(xhtml: &str, first_page: u32) -> Result<LayoutDocument, std::io::Error> {
+    let mut pages: Vec<LayoutPage> = Vec::new();
+    let mut word: Option<Word> = None;
+
+    let mut reader = XmlReader::new(xhtml);
+    while let Some(event) = reader.next_event()? {
+        match event {
+            XmlEvent::Start { name, attributes } => match name.as_str() {
+                "page" => pages.push(LayoutPage {
+                    number: first_page + pages.len() as u32,
+                    width: number_attribute(&name, &attributes, "width")?,
+                    height: number_attribute(&name, &attributes, "height")?,
+                    flows: Vec::new(),
+                }),
+                "flow" => open(&mut pages, &name)?.flows.push(LayoutFlow {
+                    rect: Rect::default(),
+                    blocks: Vec::new(),
+                }),
+                "block" => {
+                    let rect = rect_attributes(&name, &attributes)?;
+                    let page = open(&mut pages, &name)?;
+                    open(&mut page.flows, &name)?.blocks.push(LayoutBlock {
+                        rect,
+                        lines: Vec::new(),
+                    });
+                }
+                "line" => {
+                    let rect = rect_attributes(&name, &attributes)?;
+                    open_block(&mut pages, &name)?.lines.push(LayoutLine {
+                        rect,
+                        words: Vec::new(),
+                    });
+                }
+                "word" => {
+                    word = Some(Word {
+                        text: String::new(),
+                        rect: rect_attributes(&name, &attributes)?,
+                    })
+                }
+                _ => {}
+            },
+            XmlEvent::Text(text) => {
+                if let Some(word) = word.as_mut() {
+                    word.text.push_str(&text);
+                }
+            }
+            XmlEvent::End { name } => match name.as_str() {
+                "word" => {
+                    let block = open_block(&mut pages, &name)?;
+                    open(&mut block.lines, &name)?.words.extend(word.take());
+                }
+                "flow" => {
+                    let flow = open(&mut open(&mut pages, &name)?.flows, &name)?;
+                    flow.rect = bounding_rect(flow.blocks.iter().map(|block| block.rect));
+                }
+                _ => {}
+            },
+        }
+    }
+
+    Ok(LayoutDocument { pages })
+}
+
+// the element the next one goes into
+fn open<'a, T>(elements: &'a mut [T], child: &str) -> Result<&'a mut T, std::io::Error> {
+    elements
+        .last_mut()
+        .ok_or_else(|| unexpected_output(&format!("<{}> outside its parent", child)))
+}
+
+fn open_block<'a>(
+    pages: &'a mut [LayoutPage],
+    child: &str,
+) -> Result<&'a mut LayoutBlock, std::io::Error> {
+    let page = open(pages, child)?;
+    let flow = open(&mut page.flows, child)?;
+    open(&mut flow.blocks, child)
+}
+
+// the smallest rect around all of them, an empty one at 0,0 for none
+fn bounding_rect(rects: impl Iterator<Item = Rect>) -> Rect {
+    rects.reduce(|a, b| a.union(&b)).unwrap_or_default()
+}
@@ -0,0 +1,300 @@
+use crate::utils::{run_program, run_program_raw, PopplerFile};
+use std::future::Future;
+
+mod layout;
+mod region;
+mod tsv;
+mod words;
+
+pub use crate::utils::Rect;
+pub use layout::{LayoutBlock, LayoutDocument, LayoutFlow, LayoutLine, LayoutPage};
+pub use region::{extract_region, extract_regions, Units};
+pub use tsv::{group_tsv_rows, TsvBlock, TsvLevel, TsvLine, TsvRow};
+pub use words::{PageWords, Word};
+
+pub(crate) use layout::parse_layout;
+#[cfg(test)]
+pub(crate) use region::crop_args;
+pub(crate) use tsv::parse_tsv;
+pub(crate) use words::parse_words;
+
+/*
+/fix Replace the paramaters with the ones in the list below. The replaced parameters should match the formatting.
+
+For example,
+`boundingBoxXhtml: { arg: "-bbox", type: "boolean" },` should translate to `bounding_box_xhtml: bool, // boundingBoxXhtml, -bbox`
+
+Items to translate:
+```
+boundingBoxXhtml: { arg: "-bbox", type: "boolean" },
+boundingBoxXhtmlLayout: {arg: "-bbox-layout", type: "boolean"},
+ ```
+*/
+pub struct PdfToTextConfig<'a> {
+    pub bounding_box_xhtml: bool,        // bbox, -bbox
+    pub bounding_box_xhtml_layout: bool, // bboxLayout, -bbox-layout
+    pub crop_box: bool,                  // useCropBox, -cropbox
+    pub h: f64,                          // h, -H
+    pub w: f64,                          // w, -W
+    pub x: f64,                          // x, -x
+    pub y: f64,                          // y, -y
+    pub eol_convention: &'a str,         // textEOLStr, -eol
+    pub first_page_to_convert: i32,      // firstPage, -f
+    pub fixed_width_layout: f64,         // fixedPitch, -fixed
+    pub generate_html_meta_file: bool,   // htmlMeta, -htmlmeta
+    pub generate_tsv_file: bool,         // tsvMode, -tsv
+    pub last_page_to_convert: i32,       // lastPage, -l
+    pub list_encoding_options: bool,     // printEnc, -listenc
+    pub maintain_layout: bool,           // physLayout, -layout
+    pub no_diagonal_text: bool,          // discardDiag, -nodiag
+    pub no_page_breaks: bool,            // noPageBreaks, -nopgbrk
+    pub output_encoding: &'a str,        // textEncName, -enc
+    pub owner_password: &'a str,         // ownerPassword, -opw
+    pub print_version_info: bool,        // printVersion, -v
+    pub quiet: bool,                     // quiet, -q
+    pub raw_layout: bool,                // rawOrder, -raw
+    pub resolution: f64,                 // resolution, -r
+    pub user_password: &'a str,          // userPassword, -upw
+    pub print_help: bool,                // printHelp, -h, -help, --help, -?
+    pub column_spacing: f64,             // colspacing, -colspacing
+}
+
+// the text of one page, as pdftotext writes it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageText {
+    pub number: u32,
+    pub text: String,
+}
+
+impl<'a> Default for PdfToTextConfig<'a> {
+    fn default() -> Self {
+        Self {
+            bounding_box_xhtml: false, // true makes htmlMeta true
+            bounding_box_xhtml_layout: false,
+            crop_box: false,
+            h: 0.0, // in pixels
+            w: 0.0, // in pixels
+            x: 0.0,
+            y: 0.0,
+            column_spacing: 0.7, // max 10
+            eol_convention: "",  // unix, dos, mac
+            first_page_to_convert: 1,
+            fixed_width_layout: 0.0, // >0 makes physLayout true. In points defining the width of a single character
+            generate_html_meta_file: false,
+            generate_tsv_file: false, // true make also true physLayout, fixedPitch, rawOrder, htmlMeta, discardDiag
+            last_page_to_convert: 0,
+            list_encoding_options: false,
+            maintain_layout: false,
+            no_diagonal_text: false,
+            no_page_breaks: false,
+            output_encoding: "",
+            owner_password: "",
+            print_version_info: false,
+            quiet: false,
+            raw_layout: false,
+            resolution: 72.0, // in dpi
+            user_password: "",
+            print_help: false,
+        }
+    }
+}
+
+pub fn pdf_to_text(
+    file: PopplerFile,
+    options: PdfToTextConfig<'static>,
+) -> impl Future<Output = Result<String, std::io::Error>> {
+    let parsed_options = parse_options(&options);
+
+    // return the non-awaited future
+    run_program(file, "pdftotext", parsed_options)
+}
+
+// Same as pdf_to_text, split into pages at the form feeds pdftotext ends
+// every page with. no_page_breaks is ignored. Pages without text are kept,
+// and nothing is trimmed.
+pub fn pdf_to_text_pages(
+    file: PopplerFile,
+    mut options: PdfToTextConfig<'static>,
+) -> impl Future<Output = Result<Vec<PageText>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.no_page_breaks = false;
+    let first_page = first_page_number(&options);
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout =
+            run_program_raw(file, "pdftotext", parsed_options, vec!["-".to_string()]).await?;
+        Ok(parse_text_pages(
+            &String::from_utf8_lossy(&stdout),
+            first_page,
+        ))
+    }
+}
+
+// The words of -bbox with their boxes, one PageWords per converted page.
+// The -bbox-layout, -tsv and -htmlmeta modes replace that output, so they are
+// ignored here. The resolution is always 72 dpi, pdftotext scales the boxes
+// by it and they are in points at 72.
+pub fn pdf_to_words(
+    file: PopplerFile,
+    mut options: PdfToTextConfig<'static>,
+) -> impl Future<Output = Result<Vec<PageWords>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.resolution = 72.0;
+    options.bounding_box_xhtml = true;
+    let first_page = first_page_number(&options);
+    let parsed_options = parse_options(&options);
+
+    async move {
+        // "-" writes to stdout instead of a file next to the PDF
+        let stdout =
+            run_program_raw(file, "pdftotext", parsed_options, vec!["-".to_string()]).await?;
+        parse_words(&String::from_utf8_lossy(&stdout), first_page)
+    }
+}
+
+// Same as pdf_to_words, with the words grouped into the flows, blocks and
+// lines of -bbox-layout. Also always at 72 dpi.
+pub fn pdf_to_layout(
+    file: PopplerFile,
+    mut options: PdfToTextConfig<'static>,
+) -> impl Future<Output = Result<LayoutDocument, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.resolution = 72.0;
+    options.bounding_box_xhtml_layout = true;
+    let first_page = first_page_number(&options);
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout =
+            run_program_raw(file, "pdftotext", parsed_options, vec!["-".to_string()]).await?;
+        parse_layout(&String::from_utf8_lossy(&stdout), first_page)
+    }
+}
+
+// The rows of -tsv, Tesseract's TSV format, for tools made for OCR output.
+// Always at 72 dpi, like pdf_to_words.
+pub fn pdf_to_tsv(
+    file: PopplerFile,
+    mut options: PdfToTextConfig<'static>,
+) -> impl Future<Output = Result<Vec<TsvRow>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.resolution = 72.0;
+    options.generate_tsv_file = true;
+    let parsed_options = parse_options(&options);
+
+    async move {
+        let stdout =
+            run_program_raw(file, "pdftotext", parsed_options, vec!["-".to_string()]).await?;
+        parse_tsv(&String::from_utf8_lossy(&stdout))
+    }
+}
+
+// "page 1\x0cpage 2\x0c", the pages count up from first_page
+pub(crate) fn parse_text_pages(stdout: &str, first_page: u32) -> Vec<PageText> {
+    if stdout.is_empty() {
+        return Vec::new();
+    }
+    let stdout = stdout.strip_suffix('\x0c').unwrap_or(stdout);
+
+    stdout
+        .split('\x0c')
+        .zip(first_page..)
+        .map(|(text, number)| PageText {
+            number,
+            text: text.to_string(),
+        })
+        .collect()
+}
+
+// the modes that replace the plain text pdftotext prints
+fn clear_output_modes(options: &mut PdfToTextConfig) {
+    options.bounding_box_xhtml = false;
+    options.bounding_box_xhtml_layout = false;
+    options.generate_html_meta_file = false;
+    options.generate_tsv_file = false;
+}
+
+// the number of the first page pdftotext converts, it starts at page 1 for
+// anything smaller
+fn first_page_number(options: &PdfToTextConfig) -> u32 {
+    options.first_page_to_convert.max(1) as u32
+}
+
+fn parse_options(options: &PdfToTextConfig) -> Vec<String> {
+    let mut parsed_options = Vec::new();
+
+    macro_rules! add_option {
+        ($condition:expr, $arg:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+            }
+        };
+        ($condition:expr, $arg:expr, $value:expr) => {
+            if $condition {
+                parsed_options.push($arg.to_string());
+                parsed_options.push($value.to_string());
+            }
+        };
+    }
+    add_option!(options.bounding_box_xhtml, "-bbox");
+    add_option!(options.bounding_box_xhtml_layout, "-bbox-layout");
+    add_option!(options.crop_box, "-cropbox");
+    add_option!(options.h != 0.0, "-H", options.h);
+    add_option!(options.w != 0.0, "-W", options.w);
+    add_option!(options.x != 0.0, "-x", options.x);
+    add_option!(options.y != 0.0, "-y", options.y);
+    add_option!(
+        !options.eol_convention.is_empty(),
+        "-eol",
+        options.eol_convention
+    );
+    add_option!(
+        options.first_page_to_convert != 1,
+        "-f",
+        options.first_page_to_convert
+    );
+    add_option!(
+        options.fixed_width_layout != 0.0,
+        "-fixed",
+        options.fixed_width_layout
+    );
+    add_option!(options.generate_html_meta_file, "-htmlmeta");
+    add_option!(options.generate_tsv_file, "-tsv");
+    add_option!(
+        options.last_page_to_convert != 0,
+        "-l",
+        options.last_page_to_convert
+    );
+    add_option!(options.list_encoding_options, "-listenc");
+    add_option!(options.maintain_layout, "-layout");
+    add_option!(options.no_diagonal_text, "-nodiag");
+    add_option!(options.no_page_breaks, "-nopgbrk");
+    add_option!(
+        !options.output_encoding.is_empty(),
+        "-enc",
+        options.output_encoding
+    );
+    add_option!(
+        !options.owner_password.is_empty(),
+        "-opw",
+        options.owner_password
+    );
+    add_option!(options.print_version_info, "-v");
+    add_option!(options.quiet, "-q");
+    add_option!(options.raw_layout, "-raw");
+    add_option!(options.resolution != 72.0, "-r", options.resolution);
+    add_option!(
+        !options.user_password.is_empty(),
+        "-upw",
+        options.user_password
+    );
+    add_option!(options.print_help, "-h");
+    add_option!(
+        options.column_spacing != 0.7,
+        "-colspacing",
+        options.column_spacing
+    );
+
+    parsed_options
+}
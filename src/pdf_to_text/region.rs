@@ -0,0 +1,137 @@
+use super::{clear_output_modes, parse_options, PdfToTextConfig};
+use crate::utils::{run_program_raw, PopplerFile, Rect};
+use std::collections::BTreeMap;
+use std::future::Future;
+
+// what the numbers of a region are in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Units {
+    Points,
+    Millimetres,
+    PixelsAt(f64), // at that many dpi, as on a page image rendered with it
+}
+
+impl Units {
+    // the -r the crop is given at
+    fn resolution(self) -> f64 {
+        match self {
+            Units::Points | Units::Millimetres => 72.0,
+            Units::PixelsAt(dpi) => dpi,
+        }
+    }
+
+    fn to_pixels(self, value: f64) -> f64 {
+        match self {
+            Units::Points | Units::PixelsAt(_) => value,
+            Units::Millimetres => value * 72.0 / 25.4,
+        }
+    }
+}
+
+// The text inside region of one page, with y counting down from the top of
+// the page's MediaBox. pdftotext always crops relative to the MediaBox, so
+// crop_box is cleared along with x, y, w, h, resolution, the page range and
+// the output modes of options. The text is trimmed.
+pub fn extract_region(
+    file: PopplerFile,
+    page: u32,
+    region: Rect,
+    units: Units,
+    options: PdfToTextConfig<'static>,
+) -> impl Future<Output = Result<String, std::io::Error>> {
+    let regions = extract_regions(file, page, &[("", region)], units, options);
+
+    async move {
+        let mut texts = regions.await?;
+        Ok(texts.remove("").unwrap_or_default())
+    }
+}
+
+// Same as extract_region for several regions of the page, by their names,
+// which must differ. pdftotext crops to one region at a time, so it runs once
+// per region.
+pub fn extract_regions(
+    file: PopplerFile,
+    page: u32,
+    regions: &[(&str, Rect)],
+    units: Units,
+    mut options: PdfToTextConfig<'static>,
+) -> impl Future<Output = Result<BTreeMap<String, String>, std::io::Error>> {
+    clear_output_modes(&mut options);
+    options.crop_box = false;
+    options.first_page_to_convert = page as i32;
+    options.last_page_to_convert = page as i32;
+    options.x = 0.0;
+    options.y = 0.0;
+    options.w = 0.0;
+    options.h = 0.0;
+    options.resolution = units.resolution();
+    let parsed_options = parse_options(&options);
+
+    let regions: Vec<(String, Rect)> = regions
+        .iter()
+        .map(|(name, region)| (name.to_string(), *region))
+        .collect();
+
+    async move {
+        // every region is checked before pdftotext runs for any of them
+        let mut crops: Vec<(String, Vec<String>)> = Vec::new();
+        for (name, region) in regions {
+            if crops.iter().any(|(other, _)| *other == name) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Region {:?} is given more than once", name),
+                ));
+            }
+            crops.push((name, crop_args(page, &region, units)?));
+        }
+
+        let mut texts = BTreeMap::new();
+        for (name, crop) in crops {
+            let mut args = parsed_options.clone();
+            args.extend(crop);
+            let stdout =
+                run_program_raw(file.clone(), "pdftotext", args, vec!["-".to_string()]).await?;
+            let text = String::from_utf8_lossy(&stdout);
+            texts.insert(name, text.trim_end_matches('\x0c').trim().to_string());
+        }
+        Ok(texts)
+    }
+}
+
+// "-x 10 -y 20 -W 101 -H 40". pdftotext only takes whole pixels, the crop
+// is rounded outwards so nothing inside the region is cut off.
+pub(crate) fn crop_args(
+    page: u32,
+    region: &Rect,
+    units: Units,
+) -> Result<Vec<String>, std::io::Error> {
+    let x_min = units.to_pixels(region.x_min).floor();
+    let y_min = units.to_pixels(region.y_min).floor();
+    let x_max = units.to_pixels(region.x_max).ceil();
+    let y_max = units.to_pixels(region.y_max).ceil();
+
+    let is_valid = page > 0
+        && units.resolution() > 0.0
+        && x_min >= 0.0
+        && y_min >= 0.0
+        && x_max > x_min
+        && y_max > y_min;
+    if !is_valid {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Not a valid region: {:?} on page {}", region, page),
+        ));
+    }
+
+    Ok(vec![
+        "-x".to_string(),
+        x_min.to_string(),
+        "-y".to_string(),
+        y_min.to_string(),
+        "-W".to_string(),
+        (x_max - x_min).to_string(),
+        "-H".to_string(),
+        (y_max - y_min).to_string(),
+    ])
+}
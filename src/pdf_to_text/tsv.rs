@@ -0,0 +1,177 @@
+use super::words::unexpected_output;
+use crate::utils::Rect;
+
+// One row of -tsv, in the columns Tesseract writes. Only Word rows have
+// text, pdftotext's "###PAGE###", "###FLOW###" and "###LINE###" markers are
+// left out. Boxes are in points, top counts down from the top of the page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TsvRow {
+    pub level: TsvLevel,
+    pub page_num: u32,
+    pub par_num: u32, // the flow for pdftotext
+    pub block_num: u32,
+    pub line_num: u32,
+    pub word_num: u32,
+    pub left: f64,
+    pub top: f64,
+    pub width: f64,
+    pub height: f64,
+    pub conf: f64, // -1 for everything but words, pdftotext always gives 100
+    pub text: String,
+}
+
+// Tesseract's levels. pdftotext writes Page, Paragraph, Line and Word rows,
+// never Block rows. Its Paragraph rows are the "###FLOW###" markers, written
+// once per block despite the name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TsvLevel {
+    Page = 1,
+    Block = 2,
+    Paragraph = 3,
+    Line = 4,
+    Word = 5,
+}
+
+impl TsvLevel {
+    fn from_number(number: &str) -> Option<Self> {
+        match number {
+            "1" => Some(TsvLevel::Page),
+            "2" => Some(TsvLevel::Block),
+            "3" => Some(TsvLevel::Paragraph),
+            "4" => Some(TsvLevel::Line),
+            "5" => Some(TsvLevel::Word),
+            _ => None,
+        }
+    }
+}
+
+impl TsvRow {
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x_min: self.left,
+            y_min: self.top,
+            x_max: self.left + self.width,
+            y_max: self.top + self.height,
+        }
+    }
+}
+
+// the words of a block as grouped by group_tsv_rows, rect is the one around
+// its words
+#[derive(Debug, Clone, PartialEq)]
+pub struct TsvBlock<'a> {
+    pub page_num: u32,
+    pub par_num: u32,
+    pub block_num: u32,
+    pub rect: Rect,
+    pub lines: Vec<TsvLine<'a>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TsvLine<'a> {
+    pub line_num: u32,
+    pub rect: Rect,
+    pub words: Vec<&'a TsvRow>,
+}
+
+impl TsvBlock<'_> {
+    // one line of text per line
+    pub fn text(&self) -> String {
+        let lines: Vec<String> = self.lines.iter().map(TsvLine::text).collect();
+        lines.join("\n")
+    }
+}
+
+impl TsvLine<'_> {
+    // the words separated by single spaces
+    pub fn text(&self) -> String {
+        let words: Vec<&str> = self.words.iter().map(|word| word.text.as_str()).collect();
+        words.join(" ")
+    }
+}
+
+// The Word rows put back together into lines and blocks by their numbers,
+// in the order of the rows. The other rows are not needed for that.
+pub fn group_tsv_rows(rows: &[TsvRow]) -> Vec<TsvBlock<'_>> {
+    let mut blocks: Vec<TsvBlock> = Vec::new();
+    for row in rows.iter().filter(|row| row.level == TsvLevel::Word) {
+        let key = (row.page_num, row.par_num, row.block_num);
+        let block = match blocks.last_mut() {
+            Some(block) if (block.page_num, block.par_num, block.block_num) == key => block,
+            _ => {
+                blocks.push(TsvBlock {
+                    page_num: row.page_num,
+                    par_num: row.par_num,
+                    block_num: row.block_num,
+                    rect: row.rect(),
+                    lines: Vec::new(),
+                });
+                blocks.last_mut().unwrap()
+            }
+        };
+        block.rect = block.rect.union(&row.rect());
+
+        match block.lines.last_mut() {
+            Some(line) if line.line_num == row.line_num => {
+                line.rect = line.rect.union(&row.rect());
+                line.words.push(row);
+            }
+            _ => block.lines.push(TsvLine {
+                line_num: row.line_num,
+                rect: row.rect(),
+                words: vec![row],
+            }),
+        }
+    }
+    blocks
+}
+
+// A header line and twelve tab separated columns per row. The text is the
+// last column and taken as it is, tabs and quotes included, since pdftotext
+// neither quotes nor escapes it. A line that is not a row continues the text
+// of the word before it, the word had a line break.
+pub(crate) fn parse_tsv(stdout: &str) -> Result<Vec<TsvRow>, std::io::Error> {
+    let mut rows: Vec<TsvRow> = Vec::new();
+    for line in stdout.lines() {
+        if line.starts_with("level\t") || line.is_empty() {
+            continue;
+        }
+        match (parse_row(line), rows.last_mut()) {
+            (Some(row), _) => rows.push(row),
+            (None, Some(word)) if word.level == TsvLevel::Word => {
+                word.text.push('\n');
+                word.text.push_str(line);
+            }
+            (None, _) => return Err(unexpected_output(line)),
+        }
+    }
+    Ok(rows)
+}
+
+fn parse_row(line: &str) -> Option<TsvRow> {
+    let columns: Vec<&str> = line.splitn(12, '\t').collect();
+    let [level, page_num, par_num, block_num, line_num, word_num, left, top, width, height, conf, text] =
+        columns[..]
+    else {
+        return None;
+    };
+
+    let level = TsvLevel::from_number(level)?;
+    Some(TsvRow {
+        level,
+        page_num: page_num.parse().ok()?,
+        par_num: par_num.parse().ok()?,
+        block_num: block_num.parse().ok()?,
+        line_num: line_num.parse().ok()?,
+        word_num: word_num.parse().ok()?,
+        left: left.parse().ok()?,
+        top: top.parse().ok()?,
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+        conf: conf.parse().ok()?,
+        text: match level {
+            TsvLevel::Word => text.to_string(),
+            _ => String::new(),
+        },
+    })
+}
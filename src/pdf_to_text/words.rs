@@ -0,0 +1,91 @@
+use crate::utils::xml::{XmlEvent, XmlReader};
+use crate::utils::Rect;
+
+// one <page> of -bbox, its size in points
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageWords {
+    pub number: u32,
+    pub width: f64,
+    pub height: f64,
+    pub words: Vec<Word>,
+}
+
+// rect counts y down from the top of the page
+#[derive(Debug, Clone, PartialEq)]
+pub struct Word {
+    pub text: String,
+    pub rect: Rect,
+}
+
+// The XHTML of -bbox, "<page width=\"612.000000\" height=\"792.000000\">"
+// elements with one "<word xMin=\"..\" yMin=\"..\" xMax=\"..\" yMax=\"..\">"
+// per word, in reading order. pdftotext numbers nothing, the pages count up
+// from first_page.
+pub(crate) fn parse_words(xhtml: &str, first_page: u32) -> Result<Vec<PageWords>, std::io::Error> {
+    let mut pages: Vec<PageWords> = Vec::new();
+    let mut word: Option<Word> = None;
+
+    let mut reader = XmlReader::new(xhtml);
+    while let Some(event) = reader.next_event()? {
+        match event {
+            XmlEvent::Start { name, attributes } if name == "page" => {
+                pages.push(PageWords {
+                    number: first_page + pages.len() as u32,
+                    width: number_attribute(&name, &attributes, "width")?,
+                    height: number_attribute(&name, &attributes, "height")?,
+                    words: Vec::new(),
+                });
+            }
+            XmlEvent::Start { name, attributes } if name == "word" => {
+                word = Some(Word {
+                    text: String::new(),
+                    rect: rect_attributes(&name, &attributes)?,
+                });
+            }
+            XmlEvent::Text(text) => {
+                if let Some(word) = word.as_mut() {
+                    word.text.push_str(&text);
+                }
+            }
+            XmlEvent::End { name } if name == "word" => {
+                let page = pages.last_mut().ok_or_else(|| unexpected_output(&name))?;
+                page.words.extend(word.take());
+            }
+            _ => {}
+        }
+    }
+
+    Ok(pages)
+}
+
+// xMin, yMin, xMax and yMax
+pub(super) fn rect_attributes(
+    element: &str,
+    attributes: &[(String, String)],
+) -> Result<Rect, std::io::Error> {
+    Ok(Rect {
+        x_min: number_attribute(element, attributes, "xMin")?,
+        y_min: number_attribute(element, attributes, "yMin")?,
+        x_max: number_attribute(element, attributes, "xMax")?,
+        y_max: number_attribute(element, attributes, "yMax")?,
+    })
+}
+
+pub(super) fn number_attribute(
+    element: &str,
+    attributes: &[(String, String)],
+    name: &str,
+) -> Result<f64, std::io::Error> {
+    attributes
+        .iter()
+        .find(|(attribute, _)| attribute == name)
+        .and_then(|(_, value)| value.parse().ok())
+        .ok_or_else(|| unexpected_output(&format!("<{}> without a {}", element, name)))
+}
+
+pub(super) fn unexpected_output(line: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected pdftotext output: {}", line),
+    )
+}
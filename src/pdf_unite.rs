@@ -0,0 +1,48 @@
+use crate::utils::{run_program_with_args, spool_file, PopplerFile, TempDir};
+use std::ffi::OsString;
+use std::future::Future;
+
+// -v only prints the version and writes no file, so it is left out and
+// pdfunite has no options left. Kept for options of later versions.
+#[derive(Default)]
+pub struct PdfUniteConfig {}
+
+// Merges the files in the given order. pdfunite can not read from stdin, so
+// buffers are written to a private temporary directory first.
+pub fn pdf_unite(
+    files: Vec<PopplerFile>,
+    _options: PdfUniteConfig,
+) -> impl Future<Output = Result<Vec<u8>, std::io::Error>> {
+    let has_files = !files.is_empty();
+
+    async move {
+        if !has_files {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "pdf_unite needs at least one file",
+            ));
+        }
+
+        let work_dir = TempDir::new()?;
+        let args = unite_args(files, &work_dir)?;
+
+        run_program_with_args("pdfunite", args).await?;
+        std::fs::read(work_dir.join(OUTPUT_FILE))
+    }
+}
+
+const OUTPUT_FILE: &str = "united.pdf";
+
+// the input files in order, buffers spooled as input-<index>.pdf, then the
+// output file
+pub(crate) fn unite_args(
+    files: Vec<PopplerFile>,
+    work_dir: &TempDir,
+) -> Result<Vec<OsString>, std::io::Error> {
+    let mut args = Vec::new();
+    for (index, file) in files.into_iter().enumerate() {
+        args.push(spool_file(file, work_dir, &format!("input-{}.pdf", index))?);
+    }
+    args.push(work_dir.join(OUTPUT_FILE).into());
+    Ok(args)
+}
@@ -1,20 +1,28 @@
+use std::ffi::OsString;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Stdio};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::thread;
 
+pub(crate) mod xml;
+
 // &str, String, and Path are excepted, all returning a Path
+#[derive(Clone)]
 pub enum PopplerFile {
     Path(PopplerFilePath),
     Buffer(PopplerFileBuffer),
 }
+#[derive(Clone)]
 pub struct PopplerFilePath {
     path: PathBuf,
 }
 
+#[derive(Clone)]
 pub struct PopplerFileBuffer {
     buffer: Vec<u8>,
 }
+#[allow(clippy::wrong_self_convention)]
 pub trait AsPopplerPath {
     fn as_poppler_path(self) -> PopplerFile;
 }
@@ -49,6 +57,7 @@ impl AsPopplerPath for &Path {
     }
 }
 
+#[allow(clippy::wrong_self_convention)]
 pub trait AsPopplerBuffer {
     fn as_poppler_buffer(self) -> PopplerFile;
 }
@@ -64,91 +73,312 @@ pub(crate) async fn run_program(
     prog_name: &str,
     parsed_options: Vec<String>,
 ) -> Result<String, std::io::Error> {
+    let stdout = run_program_raw(file, prog_name, parsed_options, Vec::new()).await?;
+    let stdout = String::from_utf8_lossy(&stdout).trim().to_string();
+
+    Ok(stdout)
+}
+
+// Same as run_program, but keeps stdout as untouched bytes and passes
+// output_args (output files, prefixes, etc.) after the input file.
+pub(crate) async fn run_program_raw(
+    file: PopplerFile,
+    prog_name: &str,
+    parsed_options: Vec<String>,
+    output_args: Vec<String>,
+) -> Result<Vec<u8>, std::io::Error> {
+    // determine if the file is a path or a fileBuffer and configure accordingly
+    let (input_arg, stdin) = match file {
+        PopplerFile::Buffer(file_buffer) => ("-".into(), Some(file_buffer.buffer)),
+        PopplerFile::Path(file) => (path_arg(file.path), None),
+    };
+
+    let mut args = vec![input_arg];
+    args.extend(parsed_options.into_iter().map(OsString::from));
+    args.extend(output_args.into_iter().map(OsString::from));
+
+    run_command(prog_name, args, stdin, None).await
+}
+
+// For programs that take several input files (pdfunite) or can not read
+// from stdin (pdfseparate, pdfattach, ...). The caller spools buffers with
+// spool_file and passes the paths as plain arguments.
+pub(crate) async fn run_program_with_args(
+    prog_name: &str,
+    args: Vec<OsString>,
+) -> Result<Vec<u8>, std::io::Error> {
+    run_command(prog_name, args, None, None).await
+}
+
+// Same as run_program_with_args, for programs that write their results to
+// the current directory (pdfsig -dump).
+pub(crate) async fn run_program_in_dir(
+    prog_name: &str,
+    args: Vec<OsString>,
+    dir: &TempDir,
+) -> Result<Vec<u8>, std::io::Error> {
+    run_command(prog_name, args, None, Some(&dir.path)).await
+}
+
+// Same as run_program_with_args, but hands back the exit status and both
+// outputs instead of turning a failure into an error, for programs whose
+// exit codes mean more than failure (pdfsig exits with 2 for unsigned files).
+pub(crate) async fn run_program_with_status(
+    prog_name: &str,
+    args: Vec<OsString>,
+) -> Result<Output, std::io::Error> {
+    spawn_command(prog_name, args, None, None)
+}
+
+async fn run_command(
+    prog_name: &str,
+    args: Vec<OsString>,
+    stdin_buffer: Option<Vec<u8>>,
+    current_dir: Option<&Path>,
+) -> Result<Vec<u8>, std::io::Error> {
+    let output = spawn_command(prog_name, args, stdin_buffer, current_dir)?;
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+
+    if !stderr.is_empty() {
+        eprintln!("Poppler Error: {}", &stderr);
+    }
+
+    if output.status.success() {
+        Ok(output.stdout)
+    } else if stderr.is_empty() {
+        // some programs (pdfsig) report failures on stdout
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Err(std::io::Error::other(stdout))
+    } else {
+        // we already printed stderr, so just return a generic error
+        Err(std::io::Error::other(stderr))
+    }
+}
+
+fn spawn_command(
+    prog_name: &str,
+    args: Vec<OsString>,
+    stdin_buffer: Option<Vec<u8>>,
+    current_dir: Option<&Path>,
+) -> Result<Output, std::io::Error> {
     let exe_path = get_path_to_executable(prog_name);
 
     // error if there is not a valid path to the executable.
     // Check the hard coded folder structures.
-    if let Err(_) = exe_path {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "Failed to get_path_to_executable",
-        ));
+    if exe_path.is_err() {
+        return Err(std::io::Error::other("Failed to get_path_to_executable"));
     }
 
     let mut handle = Command::new(exe_path.unwrap());
-
-    // determine if the file is a path or a fileBuffer and configure accordingly
-    match file {
-        PopplerFile::Buffer(_) => {
-            handle.arg("-");
-            handle.args(parsed_options);
-            handle.stdin(Stdio::piped());
-            handle.stdout(Stdio::piped());
-            handle.stderr(Stdio::piped());
-        }
-        PopplerFile::Path(ref file) => {
-            handle.arg(file.path.clone());
-            handle.args(parsed_options);
-            handle.stdout(Stdio::piped());
-            handle.stderr(Stdio::piped());
-        }
+    handle.args(args);
+    if let Some(current_dir) = current_dir {
+        handle.current_dir(current_dir);
     }
+    if stdin_buffer.is_some() {
+        handle.stdin(Stdio::piped());
+    }
+    handle.stdout(Stdio::piped());
+    handle.stderr(Stdio::piped());
 
     let child = handle.spawn();
 
     if let Ok(mut child) = child {
         // write file to stdin if it is a fileBuffer
-        if let PopplerFile::Buffer(file_buffer) = file {
+        // the writer is only joined after the output is read, otherwise a
+        // large output fills the stdout pipe while we are still writing
+        let mut stdin_handle = None;
+        if let Some(buffer) = stdin_buffer {
             if let Some(mut stdin) = child.stdin.take() {
-                let stdin_handle = thread::spawn(move || stdin.write_all(&file_buffer.buffer));
-                // handle error if unable to write to stdin
-                match stdin_handle.join() {
-                    Ok(Ok(())) => {
-                        // Everything is fine, continue with your code
-                    }
-                    Ok(Err(e)) => {
-                        // stdin.write_all(&file_buffer.buffer) returned an error
-                        eprintln!("Error: {:?}", e);
-                        return Err(e);
-                    }
-                    Err(_) => {
-                        // The child thread panicked
-                        let e =
-                            std::io::Error::new(std::io::ErrorKind::Other, "Child thread panicked");
-                        eprintln!("Error: {:?}", e);
-                        return Err(e);
-                    }
-                }
+                stdin_handle = Some(thread::spawn(move || stdin.write_all(&buffer)));
             }
         }
 
         // read stdout and stderr
-        if let Ok(output) = child.wait_with_output() {
-            let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
-            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let output = child.wait_with_output();
+
+        // handle error if unable to write to stdin
+        if let Some(stdin_handle) = stdin_handle {
+            match stdin_handle.join() {
+                Ok(Ok(())) => {
+                    // Everything is fine, continue with your code
+                }
+                Ok(Err(e)) => {
+                    // stdin.write_all(&buffer) returned an error
+                    eprintln!("Error: {:?}", e);
+                    return Err(e);
+                }
+                Err(_) => {
+                    // The child thread panicked
+                    let e = std::io::Error::other("Child thread panicked");
+                    eprintln!("Error: {:?}", e);
+                    return Err(e);
+                }
+            }
+        }
+
+        output.map_err(|_| std::io::Error::other("Failed to wait for output from child process"))
+    } else {
+        Err(std::io::Error::other("Failed to spawn child process"))
+    }
+}
 
-            if !stderr.is_empty() {
-                eprintln!("Poppler Error: {}", &stderr);
+// a relative path starting with '-' would be read as an option
+fn path_arg(path: PathBuf) -> OsString {
+    if path.as_os_str().to_string_lossy().starts_with('-') {
+        Path::new(".").join(path).into_os_string()
+    } else {
+        path.into_os_string()
+    }
+}
+
+// Paths are passed through as they are, buffers are written to the
+// temporary directory as `name` so programs without stdin support can
+// open them.
+pub(crate) fn spool_file(
+    file: PopplerFile,
+    dir: &TempDir,
+    name: &str,
+) -> Result<OsString, std::io::Error> {
+    match file {
+        PopplerFile::Path(file) => Ok(path_arg(file.path)),
+        PopplerFile::Buffer(file_buffer) => {
+            let path = dir.path.join(name);
+            std::fs::write(&path, file_buffer.buffer)?;
+            Ok(path.into_os_string())
+        }
+    }
+}
+
+// Same as spool_file, with relative paths made absolute, for programs run
+// in another directory (pdfsig -dump).
+pub(crate) fn spool_file_absolute(
+    file: PopplerFile,
+    dir: &TempDir,
+    name: &str,
+) -> Result<OsString, std::io::Error> {
+    let path = spool_file(file, dir, name)?;
+    Ok(std::path::absolute(path)?.into_os_string())
+}
+
+// Like spool_file, but the program always sees the file as `name`, for
+// programs that take the file name from the path (pdfattach). Paths with a
+// different file name are copied.
+pub(crate) fn spool_file_named(
+    file: PopplerFile,
+    dir: &TempDir,
+    name: &str,
+) -> Result<OsString, std::io::Error> {
+    let is_plain_name =
+        !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\']);
+    if !is_plain_name {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Not a valid file name: {}", name),
+        ));
+    }
+
+    let path = dir.path.join(name);
+    match file {
+        PopplerFile::Path(file) if file.path.file_name() == Some(name.as_ref()) => {
+            return Ok(file.path.into_os_string());
+        }
+        PopplerFile::Path(file) => {
+            std::fs::copy(&file.path, &path)?;
+        }
+        PopplerFile::Buffer(file_buffer) => {
+            std::fs::write(&path, file_buffer.buffer)?;
+        }
+    }
+    Ok(path.into_os_string())
+}
+
+// A private directory for programs that write their results to files.
+// Removed together with everything in it when dropped.
+pub(crate) struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub(crate) fn new() -> Result<Self, std::io::Error> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        loop {
+            let path = std::env::temp_dir().join(format!(
+                "poppler-utils-rs-{}-{}",
+                std::process::id(),
+                COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            match std::fs::create_dir(&path) {
+                Ok(()) => return Ok(Self { path }),
+                // left behind by an earlier process with the same id
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
             }
+        }
+    }
 
-            if output.status.success() {
-                println!("{}", &stdout);
-                Ok(stdout)
-            } else {
-                // we already printed stderr, so just return a generic error
-                Err(std::io::Error::new(std::io::ErrorKind::Other, stderr))
+    // joins a file name onto the directory, as an argument for the program
+    pub(crate) fn join(&self, name: &str) -> String {
+        self.path.join(name).to_string_lossy().to_string()
+    }
+
+    // every file in the directory as (file name, contents), sorted by name
+    pub(crate) fn read_files(&self) -> Result<Vec<(String, Vec<u8>)>, std::io::Error> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(&self.path)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                files.push((name, std::fs::read(entry.path())?));
             }
-        } else {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Failed to wait for output from child process",
-            ))
         }
-    } else {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            "Failed to spawn child process",
-        ))
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(files)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+// object number and generation of an indirect object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjectId {
+    pub num: u32,
+    pub gen: u32,
+}
+
+// A rectangle in points. pdfinfo boxes are in PDF space (y up), pdftotext
+// boxes count y down from the top of the page.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rect {
+    pub x_min: f64,
+    pub y_min: f64,
+    pub x_max: f64,
+    pub y_max: f64,
+}
+
+impl Rect {
+    pub fn width(&self) -> f64 {
+        self.x_max - self.x_min
+    }
+
+    pub fn height(&self) -> f64 {
+        self.y_max - self.y_min
+    }
+
+    // the smallest rectangle around both
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect {
+            x_min: self.x_min.min(other.x_min),
+            y_min: self.y_min.min(other.y_min),
+            x_max: self.x_max.max(other.x_max),
+            y_max: self.y_max.max(other.y_max),
+        }
     }
 }
 
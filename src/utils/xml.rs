@@ -0,0 +1,219 @@
+// A small pull reader for the XML poppler prints (XMP packets, the XHTML of
+// pdftotext -bbox). No validation and no namespace handling, names are kept
+// with their prefix.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum XmlEvent {
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    // also sent straight after the Start of a self closing <element/>
+    End {
+        name: String,
+    },
+    // with entities and character references decoded, CDATA as it is
+    Text(String),
+}
+
+pub(crate) struct XmlReader<'a> {
+    input: &'a str,
+    position: usize,
+    pending_end: Option<String>,
+}
+
+impl<'a> XmlReader<'a> {
+    pub(crate) fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            position: 0,
+            pending_end: None,
+        }
+    }
+
+    // None at the end of the input. Comments, processing instructions and
+    // the doctype are skipped.
+    pub(crate) fn next_event(&mut self) -> Result<Option<XmlEvent>, std::io::Error> {
+        if let Some(name) = self.pending_end.take() {
+            return Ok(Some(XmlEvent::End { name }));
+        }
+
+        loop {
+            let rest = &self.input[self.position..];
+            if rest.is_empty() {
+                return Ok(None);
+            }
+
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.position += end;
+                return Ok(Some(XmlEvent::Text(decode_entities(&rest[..end]))));
+            }
+
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                let end = cdata.find("]]>").ok_or_else(|| unexpected_end(rest))?;
+                self.position += "<![CDATA[".len() + end + "]]>".len();
+                return Ok(Some(XmlEvent::Text(cdata[..end].to_string())));
+            }
+
+            let skipped = if rest.starts_with("<!--") {
+                Some(rest.find("-->").map(|end| end + "-->".len()))
+            } else if rest.starts_with("<?") {
+                Some(rest.find("?>").map(|end| end + "?>".len()))
+            } else if rest.starts_with("<!") {
+                Some(skip_declaration(rest))
+            } else {
+                None
+            };
+            if let Some(skipped) = skipped {
+                self.position += skipped.ok_or_else(|| unexpected_end(rest))?;
+                continue;
+            }
+
+            let end = find_tag_end(rest).ok_or_else(|| unexpected_end(rest))?;
+            self.position += end + 1;
+            let tag = &rest[1..end];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                return Ok(Some(XmlEvent::End {
+                    name: name.trim().to_string(),
+                }));
+            }
+
+            let (tag, self_closing) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+            let name_end = tag
+                .find(|c: char| c.is_ascii_whitespace())
+                .unwrap_or(tag.len());
+            let name = tag[..name_end].to_string();
+            if name.is_empty() {
+                return Err(unexpected_markup(rest));
+            }
+            let attributes =
+                parse_attributes(&tag[name_end..]).ok_or_else(|| unexpected_markup(rest))?;
+
+            if self_closing {
+                self.pending_end = Some(name.clone());
+            }
+            return Ok(Some(XmlEvent::Start { name, attributes }));
+        }
+    }
+}
+
+// the '>' closing a tag, skipping any inside quoted attribute values
+fn find_tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in rest.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), c) if c == open => quote = None,
+            (None, '>') => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+// <!DOCTYPE ...> with an optional [internal subset]
+fn skip_declaration(rest: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (index, c) in rest.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            '>' if depth == 0 => return Some(index + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_attributes(mut rest: &str) -> Option<Vec<(String, String)>> {
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Some(attributes);
+        }
+
+        let (name, value) = rest.split_once('=')?;
+        let value = value.trim_start();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)? + 1;
+
+        attributes.push((name.trim().to_string(), decode_entities(&value[1..end])));
+        rest = &value[end + 1..];
+    }
+}
+
+// The predefined entities and character references. Anything else is kept
+// as it is, poppler does not always escape a lone '&'.
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let number = entity.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+fn unexpected_end(rest: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected end of XML: {}", preview(rest)),
+    )
+}
+
+fn unexpected_markup(rest: &str) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        format!("Unexpected XML: {}", preview(rest)),
+    )
+}
+
+fn preview(rest: &str) -> &str {
+    let end = rest
+        .char_indices()
+        .nth(40)
+        .map(|(index, _)| index)
+        .unwrap_or(rest.len());
+    &rest[..end]
+}